@@ -0,0 +1,199 @@
+//! Query attachments
+//!
+//! Text piped on stdin, `--file` arguments and live cluster context are
+//! sent to VORTEX as labeled blocks after the query. Binary input is rejected, and
+//! oversized input keeps its beginning and end with a marker in between.
+
+use crate::utils::ui;
+use anyhow::{bail, Context, Result};
+use std::io::{self, IsTerminal, Read};
+
+/// Bytes inspected when looking for binary content
+const SNIFF_LEN: usize = 8192;
+
+/// A block of text sent along with the query
+pub(super) struct Attachment {
+    /// Short name shown in the request summary
+    pub label: String,
+    /// Line introducing the block to VORTEX
+    pub heading: String,
+    pub content: String,
+    /// Size of the input before truncation, in bytes
+    pub original_size: usize,
+    pub truncated: bool,
+}
+
+impl Attachment {
+    /// Cut the content down to `limit` bytes, keeping its beginning and end
+    pub(super) fn truncate(self, limit: usize) -> Self {
+        let (content, truncated) = truncate_middle(&self.content, limit);
+        Self {
+            content,
+            truncated: self.truncated || truncated,
+            ..self
+        }
+    }
+}
+
+/// Read an attached file
+pub(super) fn from_file(path: &str, limit: usize) -> Result<Attachment> {
+    let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path))?;
+    decode(path, bytes, limit)
+}
+
+/// Read stdin when it is piped, or `None` when it is a terminal or empty
+pub(super) fn from_stdin(limit: usize) -> Result<Option<Attachment>> {
+    let mut stdin = io::stdin();
+    if stdin.is_terminal() {
+        return Ok(None);
+    }
+
+    let mut bytes = Vec::new();
+    stdin
+        .read_to_end(&mut bytes)
+        .context("Failed to read stdin")?;
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+    decode("stdin", bytes, limit).map(Some)
+}
+
+/// Check that the input is text and cut it down to `limit` bytes
+fn decode(label: &str, bytes: Vec<u8>, limit: usize) -> Result<Attachment> {
+    let sniff = &bytes[..bytes.len().min(SNIFF_LEN)];
+    if sniff.contains(&0) {
+        bail!(
+            "{} looks like a binary file; only text can be attached",
+            label
+        );
+    }
+    let original_size = bytes.len();
+    let text = String::from_utf8(bytes)
+        .map_err(|_| anyhow::anyhow!("{} is not valid UTF-8 text", label))?;
+
+    let (content, truncated) = truncate_middle(&text, limit);
+    let heading = if label == "stdin" {
+        "Input from stdin".to_string()
+    } else {
+        format!("File {}", label)
+    };
+    Ok(Attachment {
+        label: label.to_string(),
+        heading,
+        content,
+        original_size,
+        truncated,
+    })
+}
+
+/// Keep the first and last part of `text` so it fits in `limit` bytes
+fn truncate_middle(text: &str, limit: usize) -> (String, bool) {
+    if text.len() <= limit {
+        return (text.to_string(), false);
+    }
+
+    let half = limit / 2;
+    let mut head_end = half;
+    while !text.is_char_boundary(head_end) {
+        head_end -= 1;
+    }
+    let mut tail_start = text.len() - half;
+    while !text.is_char_boundary(tail_start) {
+        tail_start += 1;
+    }
+
+    // Cut at line boundaries when there is one nearby
+    let head = &text[..head_end];
+    let head = head.rfind('\n').map_or(head, |i| &head[..=i]);
+    let tail = &text[tail_start..];
+    let tail = tail.find('\n').map_or(tail, |i| &tail[i + 1..]);
+
+    let omitted = text.len() - head.len() - tail.len();
+    (
+        format!(
+            "{}[... {} omitted ...]\n{}",
+            head,
+            ui::format_size(omitted as u64),
+            tail
+        ),
+        true,
+    )
+}
+
+/// Build the message sent to VORTEX from the query and its attachments
+pub(super) fn compose(query: &str, attachments: &[Attachment]) -> String {
+    let mut message = query.trim().to_string();
+
+    for attachment in attachments {
+        // Use a fence longer than any backtick run inside the content
+        let longest = attachment
+            .content
+            .split(|c| c != '`')
+            .map(str::len)
+            .max()
+            .unwrap_or(0);
+        let fence = "`".repeat(longest.max(2) + 1);
+
+        let note = if attachment.truncated {
+            format!(
+                " (truncated, {} in total)",
+                ui::format_size(attachment.original_size as u64)
+            )
+        } else {
+            String::new()
+        };
+
+        if !message.is_empty() {
+            message.push_str("\n\n");
+        }
+        message.push_str(&format!(
+            "{}{}:\n{}\n{}\n{}",
+            attachment.heading,
+            note,
+            fence,
+            attachment.content.trim_end_matches('\n'),
+            fence
+        ));
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_input_is_rejected() {
+        assert!(decode("blob", vec![0x7f, b'E', b'L', b'F', 0, 1], 1024).is_err());
+        assert!(decode("bad", vec![0xff, 0xfe, b'a'], 1024).is_err());
+        assert!(decode("ok", b"plain text".to_vec(), 1024).is_ok());
+    }
+
+    #[test]
+    fn test_truncation_keeps_head_and_tail() {
+        let text: String = (0..100).map(|i| format!("line {}\n", i)).collect();
+        let (content, truncated) = truncate_middle(&text, 100);
+
+        assert!(truncated);
+        assert!(content.starts_with("line 0\n"));
+        assert!(content.ends_with("line 99\n"));
+        assert!(content.contains("omitted"));
+        assert!(content.len() < text.len());
+    }
+
+    #[test]
+    fn test_compose_fences_content() {
+        let attachment = Attachment {
+            label: "notes.md".to_string(),
+            heading: "File notes.md".to_string(),
+            content: "```rust\nfn main() {}\n```\n".to_string(),
+            original_size: 24,
+            truncated: false,
+        };
+        let message = compose("why?", &[attachment]);
+
+        assert!(message.starts_with("why?\n\nFile notes.md:\n````\n"));
+        assert!(message.ends_with("\n````"));
+    }
+}
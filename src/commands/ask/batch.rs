@@ -0,0 +1,616 @@
+//! Batch mode (`titan ask --batch prompts.jsonl`)
+//!
+//! Sends every prompt of a JSONL file to VORTEX with a fixed number of
+//! requests in flight, retrying transient failures with backoff. The usage
+//! budget is checked again before each prompt is sent. Models named by
+//! single prompts are resolved and checked before the first request. Each result is
+//! appended to the results file as soon as it arrives, so an interrupted
+//! run continues with `--resume`, skipping the prompts that succeeded.
+
+use crate::utils::cache;
+use crate::utils::config::{BudgetAction, Config, UsageConfig};
+use crate::utils::models;
+use crate::utils::ui;
+use crate::utils::usage;
+use crate::utils::vortex::{self, Completion, Message, Params, VortexClient};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashSet, VecDeque};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Longest wait between two attempts at one prompt
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Options accepted by batch mode
+pub struct BatchOptions {
+    /// JSONL file with one prompt per line
+    pub input: PathBuf,
+    /// Where results go [default: `<input>.results.jsonl`]
+    pub results: Option<PathBuf>,
+    /// Requests in flight at once
+    pub concurrency: usize,
+    /// Extra attempts for a prompt that failed on the way or on VORTEX's side
+    pub retries: u32,
+    /// Continue an earlier run instead of refusing to touch its results
+    pub resume: bool,
+    /// Answer repeated prompts from the response cache, if `[cache] enabled`
+    pub cache: bool,
+}
+
+/// One line of the input file
+#[derive(Debug, Clone, PartialEq)]
+struct Prompt {
+    id: String,
+    prompt: String,
+    system: Option<String>,
+    model: Option<String>,
+}
+
+/// One line of the results file
+#[derive(Debug, Serialize, Deserialize)]
+struct Outcome {
+    id: String,
+    model: String,
+    output: Option<String>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+    #[serde(default)]
+    usage: Option<Value>,
+    #[serde(default)]
+    cost: Option<f64>,
+    #[serde(default)]
+    latency_ms: Option<u64>,
+    #[serde(default)]
+    attempts: u32,
+    error: Option<String>,
+    #[serde(default)]
+    params: Option<Value>,
+    /// Answered from the response cache, without a request
+    #[serde(default)]
+    cached: bool,
+    timestamp: chrono::DateTime<Utc>,
+}
+
+/// A prompt a worker is done with
+struct Finished {
+    prompt: Prompt,
+    model: String,
+    messages: Vec<Message>,
+    result: Result<Completion>,
+    attempts: u32,
+    /// Response cache key, when the cache is in use
+    cache_key: Option<String>,
+    cached: bool,
+}
+
+/// Run every prompt not yet answered in the results file
+pub(super) async fn run(
+    vortex: VortexClient,
+    config: &Config,
+    model: &str,
+    system: Option<&str>,
+    params: &Params,
+    opts: &BatchOptions,
+    chrome: bool,
+) -> Result<()> {
+    let prompts = read_prompts(&opts.input)?;
+    let results_path = opts
+        .results
+        .clone()
+        .unwrap_or_else(|| opts.input.with_extension("results.jsonl"));
+    let done = prepare_results(&results_path, opts.resume)?;
+    let mut pending: VecDeque<Prompt> = prompts
+        .iter()
+        .filter(|p| !done.contains(&p.id))
+        .cloned()
+        .collect();
+    check_models(&vortex, config, params, &mut pending).await?;
+
+    // Refusing happens here or between prompts; a warning is given once
+    let warned = Arc::new(AtomicBool::new(false));
+    if let Some(message) = over_budget(&config.usage)? {
+        if config.usage.on_exceeded == BudgetAction::Refuse {
+            bail!("{}; batch not started (see `titan usage`)", message);
+        }
+        ui::eprint_warning(&message);
+        warned.store(true, Ordering::Relaxed);
+    }
+
+    if chrome {
+        println!();
+        ui::print_header("VORTEX BATCH");
+        println!();
+        println!("  {} {}", "Input:".cyan().bold(), opts.input.display());
+        println!("  {} {}", "Model:".dimmed(), model);
+        let settings = params.describe();
+        if !settings.is_empty() {
+            println!("  {} {}", "Params:".dimmed(), settings.join(", "));
+        }
+        println!(
+            "  {} {} of {} to run ({} already done), {} at a time",
+            "Prompts:".dimmed(),
+            pending.len(),
+            prompts.len(),
+            prompts.len() - pending.len(),
+            opts.concurrency
+        );
+        println!("  {} {}", "Results:".dimmed(), results_path.display());
+        println!();
+    }
+
+    let total = pending.len();
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&results_path)
+        .with_context(|| format!("Failed to open {}", results_path.display()))?;
+
+    let progress = if chrome {
+        ProgressBar::new(total as u64)
+    } else {
+        ProgressBar::hidden()
+    };
+    progress.set_style(
+        ProgressStyle::default_bar()
+            .template("  {spinner:.cyan} [{bar:40.cyan/dim}] {pos}/{len} {msg} ({eta} left)")
+            .unwrap()
+            .progress_chars("━━╸")
+            .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
+    );
+    progress.enable_steady_tick(Duration::from_millis(80));
+
+    // Workers pull prompts from a shared queue and report back here, so
+    // only this task writes the results file
+    let use_cache = opts.cache && config.cache.enabled;
+    let vortex = Arc::new(vortex);
+    let queue = Arc::new(Mutex::new(pending));
+    let halted: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut workers = Vec::new();
+    for _ in 0..opts.concurrency.min(total) {
+        let vortex = Arc::clone(&vortex);
+        let queue = Arc::clone(&queue);
+        let tx = tx.clone();
+        let model = model.to_string();
+        let system = system.map(str::to_string);
+        let retries = opts.retries;
+        let (params, cache_config) = (params.clone(), config.cache.clone());
+        let (endpoint, context) = (config.vortex.url.clone(), config.context());
+        let usage_config = config.usage.clone();
+        let (halted, warned) = (Arc::clone(&halted), Arc::clone(&warned));
+        let progress = progress.clone();
+        workers.push(tokio::spawn(async move {
+            loop {
+                if halted.lock().unwrap().is_some() {
+                    break;
+                }
+                let Some(prompt) = queue.lock().unwrap().pop_front() else {
+                    break;
+                };
+                let model = prompt.model.clone().unwrap_or_else(|| model.clone());
+                let messages = messages_for(&prompt, system.as_deref());
+
+                // An unreadable cache entry only means the prompt is sent
+                let started = std::time::Instant::now();
+                let cache_key =
+                    use_cache.then(|| cache::key(&endpoint, &context, &model, &params, &messages));
+                let hit = cache_key
+                    .as_deref()
+                    .and_then(|key| cache::get(&cache_config, key).ok().flatten());
+
+                // Answers already recorded count toward the budget; cached
+                // answers cost nothing and are always served
+                if hit.is_none() {
+                    if let Some(message) = over_budget(&usage_config).ok().flatten() {
+                        if usage_config.on_exceeded == BudgetAction::Refuse {
+                            *halted.lock().unwrap() = Some(message);
+                            break;
+                        }
+                        if !warned.swap(true, Ordering::Relaxed) {
+                            progress.suspend(|| ui::eprint_warning(&message));
+                        }
+                    }
+                }
+                let (result, attempts) = match hit {
+                    Some(ref entry) => (Ok(entry.completion(started.elapsed())), 0),
+                    None => complete(&vortex, &model, &messages, retries).await,
+                };
+                let finished = Finished {
+                    prompt,
+                    model,
+                    messages,
+                    result,
+                    attempts,
+                    cache_key,
+                    cached: hit.is_some(),
+                };
+                if tx.send(finished).is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+    drop(tx);
+
+    let params_json = serde_json::to_value(params).ok();
+    let (mut succeeded, mut failed, mut from_cache) = (0, 0, 0);
+    let interrupted = loop {
+        let received = tokio::select! {
+            received = rx.recv() => received,
+            _ = tokio::signal::ctrl_c() => break true,
+        };
+        let Some(finished) = received else {
+            break false;
+        };
+        let Finished {
+            prompt,
+            model,
+            messages,
+            result,
+            attempts,
+            cache_key,
+            cached,
+        } = finished;
+
+        let mut outcome = match result {
+            Ok(completion) => {
+                let mut record = usage::Record::new(&completion, &messages, None, &config.usage);
+                let stored = match cache_key {
+                    // A cached answer costs nothing and is left out of the ledger
+                    _ if cached => {
+                        record.cost = Some(0.0);
+                        from_cache += 1;
+                        Ok(())
+                    }
+                    Some(ref key) => cache::put(key, &completion),
+                    None => Ok(()),
+                };
+                if let Err(e) = stored {
+                    progress.suspend(|| {
+                        ui::eprint_warning(&format!("Could not update the response cache: {:#}", e))
+                    });
+                }
+                if !cached {
+                    if let Err(e) = usage::record(&record) {
+                        progress.suspend(|| {
+                            ui::eprint_warning(&format!("Could not record usage: {:#}", e))
+                        });
+                    }
+                }
+                succeeded += 1;
+                Outcome {
+                    cached,
+                    ..success(prompt.id, &completion, &record, attempts)
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                Outcome {
+                    id: prompt.id,
+                    model,
+                    output: None,
+                    finish_reason: None,
+                    usage: None,
+                    cost: None,
+                    latency_ms: None,
+                    attempts,
+                    error: Some(format!("{:#}", e)),
+                    params: None,
+                    cached: false,
+                    timestamp: Utc::now(),
+                }
+            }
+        };
+        outcome.params = params_json.clone();
+        writeln!(file, "{}", serde_json::to_string(&outcome)?)
+            .with_context(|| format!("Failed to write {}", results_path.display()))?;
+
+        progress.inc(1);
+        if failed > 0 {
+            progress.set_message(format!("{} failed", failed).red().to_string());
+        }
+    };
+
+    for worker in &workers {
+        worker.abort();
+    }
+    progress.finish_and_clear();
+    if use_cache {
+        if let Err(e) = cache::prune(&config.cache) {
+            ui::eprint_warning(&format!("Could not prune the response cache: {:#}", e));
+        }
+    }
+
+    let remaining = total - succeeded - failed;
+    let halted = halted.lock().unwrap().take();
+    if chrome {
+        println!(
+            "  {} {} succeeded{}, {} failed{}",
+            if failed == 0 && !interrupted && halted.is_none() {
+                "✓".green().bold()
+            } else {
+                "⚠".yellow().bold()
+            },
+            succeeded,
+            if from_cache > 0 {
+                format!(" ({} from cache)", from_cache)
+            } else {
+                String::new()
+            },
+            failed,
+            if interrupted || halted.is_some() {
+                format!(", {} not run", remaining)
+            } else {
+                String::new()
+            }
+        );
+        println!(
+            "  {} {}",
+            "Results:".dimmed(),
+            results_path.display().to_string().cyan()
+        );
+        println!();
+    }
+
+    if interrupted {
+        bail!(
+            "Batch interrupted with {} prompt(s) left; rerun with --resume to finish it",
+            remaining
+        );
+    }
+    if let Some(message) = halted {
+        bail!(
+            "{}; {} prompt(s) not sent (see `titan usage`), rerun with --resume once the budget allows",
+            message,
+            remaining
+        );
+    }
+    if failed > 0 {
+        bail!(
+            "{} prompt(s) failed; rerun with --resume to retry them",
+            failed
+        );
+    }
+    Ok(())
+}
+
+/// Resolve the aliases of per-prompt models and check each distinct one
+/// against the catalog and the sampling settings, before any request is sent
+async fn check_models(
+    vortex: &VortexClient,
+    config: &Config,
+    params: &Params,
+    prompts: &mut VecDeque<Prompt>,
+) -> Result<()> {
+    let mut checked = HashSet::new();
+    for prompt in prompts.iter_mut() {
+        let Some(ref model) = prompt.model else {
+            continue;
+        };
+        let model = models::resolve_alias(model, &config.vortex.aliases);
+        if checked.insert(model.clone()) {
+            let usable = async {
+                let info = super::check_model(vortex, config, &model, false).await?;
+                params.validate(info.as_ref())
+            }
+            .await;
+            usable.map_err(|e| anyhow!("Prompt {} asks for {}: {:#}", prompt.id, model, e))?;
+        }
+        prompt.model = Some(model);
+    }
+    Ok(())
+}
+
+/// Parse the input file, requiring a prompt on every line and unique IDs
+fn read_prompts(path: &Path) -> Result<Vec<Prompt>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut prompts = Vec::new();
+    let mut ids = HashSet::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let prompt = parse_prompt(line, index + 1)
+            .with_context(|| format!("{} line {}", path.display(), index + 1))?;
+        if !ids.insert(prompt.id.clone()) {
+            bail!(
+                "{} line {}: duplicate prompt ID '{}'",
+                path.display(),
+                index + 1,
+                prompt.id
+            );
+        }
+        prompts.push(prompt);
+    }
+    if prompts.is_empty() {
+        bail!("{} contains no prompts", path.display());
+    }
+    Ok(prompts)
+}
+
+/// Read one input line; lines without an `id` are named by line number
+fn parse_prompt(line: &str, number: usize) -> Result<Prompt> {
+    let json: Value = serde_json::from_str(line).context("not valid JSON")?;
+    let text = |key: &str| json.get(key).and_then(Value::as_str).map(str::to_string);
+
+    let id = match json.get("id") {
+        None | Some(Value::Null) => number.to_string(),
+        Some(Value::String(id)) => id.clone(),
+        Some(Value::Number(id)) => id.to_string(),
+        Some(_) => bail!("\"id\" must be a string or a number"),
+    };
+    let prompt = text("prompt")
+        .or_else(|| text("query"))
+        .filter(|p| !p.trim().is_empty())
+        .ok_or_else(|| anyhow::anyhow!("missing \"prompt\""))?;
+
+    Ok(Prompt {
+        id,
+        prompt,
+        system: text("system"),
+        model: text("model"),
+    })
+}
+
+/// Check the results file and return the IDs already answered
+///
+/// On resume, failed results are dropped so their retries replace them.
+fn prepare_results(path: &Path, resume: bool) -> Result<HashSet<String>> {
+    if !path.exists() {
+        return Ok(HashSet::new());
+    }
+    if !resume {
+        bail!(
+            "{} already exists: pass --resume to continue that run, or choose another --results file",
+            path.display()
+        );
+    }
+
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut kept = String::new();
+    let mut done = HashSet::new();
+    for line in text.lines() {
+        let Ok(outcome) = serde_json::from_str::<Outcome>(line) else {
+            continue;
+        };
+        if outcome.error.is_none() && done.insert(outcome.id) {
+            kept.push_str(line);
+            kept.push('\n');
+        }
+    }
+    std::fs::write(path, kept).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(done)
+}
+
+fn messages_for(prompt: &Prompt, system: Option<&str>) -> Vec<Message> {
+    let mut messages = Vec::new();
+    if let Some(system) = prompt.system.as_deref().or(system) {
+        messages.push(Message::system(system));
+    }
+    messages.push(Message::user(prompt.prompt.as_str()));
+    messages
+}
+
+/// The budget message once a usage budget is used up
+fn over_budget(config: &UsageConfig) -> Result<Option<String>> {
+    Ok(usage::over_budget(config, &usage::load()?, None))
+}
+
+/// Request a completion, retrying transient failures with exponential
+/// backoff; errors VORTEX will give again, such as a bad request, are not
+/// retried
+async fn complete(
+    vortex: &VortexClient,
+    model: &str,
+    messages: &[Message],
+    retries: u32,
+) -> (Result<Completion>, u32) {
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let result = vortex.complete(model, messages).await;
+        match result {
+            Err(ref e) if attempt <= retries && vortex::is_transient(e) => {}
+            _ => return (result, attempt),
+        }
+        let backoff = Duration::from_secs(1 << (attempt - 1).min(5)).min(MAX_BACKOFF);
+        tokio::time::sleep(backoff).await;
+    }
+}
+
+fn success(id: String, completion: &Completion, record: &usage::Record, attempts: u32) -> Outcome {
+    Outcome {
+        id,
+        model: completion.model.clone(),
+        output: Some(completion.text.clone()),
+        finish_reason: completion.finish_reason.clone(),
+        usage: Some(serde_json::json!({
+            "prompt_tokens": record.prompt_tokens,
+            "completion_tokens": record.completion_tokens,
+            "estimated": record.estimated,
+        })),
+        cost: record.cost,
+        latency_ms: Some(completion.latency.as_millis() as u64),
+        attempts,
+        error: None,
+        params: None,
+        cached: false,
+        timestamp: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_lines() {
+        let prompt =
+            parse_prompt(r#"{"id": 7, "prompt": "Hi", "model": "vortex-lite"}"#, 1).unwrap();
+        assert_eq!(prompt.id, "7");
+        assert_eq!(prompt.model.as_deref(), Some("vortex-lite"));
+
+        let unnamed = parse_prompt(r#"{"query": "Status?"}"#, 3).unwrap();
+        assert_eq!(
+            (unnamed.id.as_str(), unnamed.prompt.as_str()),
+            ("3", "Status?")
+        );
+
+        assert!(parse_prompt(r#"{"id": "a"}"#, 1).is_err());
+        assert!(parse_prompt("not json", 1).is_err());
+
+        let with_system = Prompt {
+            system: Some("Be brief".to_string()),
+            ..unnamed
+        };
+        let contents: Vec<String> = messages_for(&with_system, Some("default"))
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(contents, ["Be brief", "Status?"]);
+    }
+
+    #[test]
+    fn test_resume_keeps_only_answered_prompts() {
+        let path =
+            std::env::temp_dir().join(format!("titan-batch-{}.results.jsonl", std::process::id()));
+        let line = |id: &str, error: Option<&str>| {
+            serde_json::json!({
+                "id": id,
+                "model": "vortex-v3",
+                "output": error.is_none().then_some("ok"),
+                "error": error,
+                "timestamp": "2026-10-18T12:00:00Z",
+            })
+            .to_string()
+        };
+        let lines = [
+            line("a", None),
+            line("b", Some("VORTEX returned 503")),
+            "{\"id\": \"c\", \"mod".to_string(),
+            line("d", None),
+        ];
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        assert!(prepare_results(&path, false).is_err());
+        let done = prepare_results(&path, true).unwrap();
+        let kept = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut done: Vec<String> = done.into_iter().collect();
+        done.sort();
+        assert_eq!(done, ["a", "d"]);
+        assert_eq!(kept, format!("{}\n{}\n", lines[0], lines[3]));
+    }
+}
@@ -0,0 +1,574 @@
+//! Model comparison (`titan ask --compare vortex-v3,vortex-v2`)
+//!
+//! Sends one prompt to several models at once and shows the answers side by
+//! side, or one after another when the terminal is too narrow for columns.
+//! With `--judge`, another request scores the answers; the judge sees them
+//! as "Answer A", "Answer B", ... so model names cannot sway it.
+
+use super::output::{OutputFormat, ResponsePrinter};
+use crate::utils::config::Config;
+use crate::utils::models;
+use crate::utils::ui;
+use crate::utils::usage;
+use crate::utils::vortex::{Completion, Message, ModelInfo, Params, VortexClient};
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use console::{pad_str, Alignment};
+use indicatif::ProgressBar;
+use serde::{Deserialize, Serialize};
+use std::io::{self, IsTerminal};
+use std::sync::Arc;
+
+/// Narrowest column worth printing side by side
+const MIN_COLUMN: usize = 30;
+
+/// Widest column, even on very wide terminals
+const MAX_COLUMN: usize = 60;
+
+/// Separator between side-by-side columns
+const GAP: &str = " │ ";
+
+/// Most models one comparison takes, one per answer label A to Z
+const MAX_MODELS: usize = 26;
+
+/// Instructions sent to the `--judge` model
+const JUDGE_PROMPT: &str = "You are an impartial judge comparing answers to the same question. \
+Score each answer from 1 to 10 for correctness, relevance and clarity, with a one-sentence reason. \
+Reply with JSON only, in the form \
+{\"scores\": [{\"answer\": \"A\", \"score\": 8, \"reason\": \"...\"}], \"winner\": \"A\"}";
+
+/// The models to compare, checked against the catalog
+pub(super) struct Lineup {
+    pub models: Vec<String>,
+    pub judge: Option<String>,
+    /// Sampling settings every model is asked with
+    params: Params,
+    /// Catalog entries, where the catalog could be fetched
+    infos: Vec<Option<ModelInfo>>,
+}
+
+impl Lineup {
+    /// Resolve aliases, drop repeats and check every model, the judge included
+    pub async fn check(
+        vortex: &VortexClient,
+        config: &Config,
+        requested: &[String],
+        judge: Option<&str>,
+        params: &Params,
+        verbose: bool,
+    ) -> Result<Self> {
+        let mut models: Vec<String> = Vec::new();
+        for model in requested {
+            let model = models::resolve_alias(model.trim(), &config.vortex.aliases);
+            if !model.is_empty() && !models.contains(&model) {
+                models.push(model);
+            }
+        }
+        if models.len() < 2 {
+            bail!("--compare needs at least two different models, e.g. vortex-v3,vortex-v2");
+        }
+        if models.len() > MAX_MODELS {
+            bail!(
+                "--compare takes at most {} models, got {}",
+                MAX_MODELS,
+                models.len()
+            );
+        }
+
+        let mut infos = Vec::new();
+        for model in &models {
+            let info = super::check_model(vortex, config, model, verbose).await?;
+            params
+                .validate(info.as_ref())
+                .with_context(|| format!("Invalid settings for {}", model))?;
+            infos.push(info);
+        }
+        let judge = match judge {
+            Some(judge) => {
+                let judge = models::resolve_alias(judge, &config.vortex.aliases);
+                super::check_model(vortex, config, &judge, verbose).await?;
+                Some(judge)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            models,
+            judge,
+            params: params.clone(),
+            infos,
+        })
+    }
+
+    /// Warn about models whose context the prompt may not fit
+    pub fn check_context(&self, messages: &[Message]) {
+        for info in &self.infos {
+            super::check_context(messages, &self.params, info.as_ref());
+        }
+    }
+}
+
+/// One model's answer, or why it has none
+struct Answer {
+    model: String,
+    outcome: std::result::Result<(Completion, usage::Record), String>,
+}
+
+/// A judge's score for one model
+#[derive(Serialize)]
+struct Score {
+    model: String,
+    score: f64,
+    reason: String,
+}
+
+/// The judge's scores, when its reply could be read
+#[derive(Serialize)]
+struct Verdict {
+    model: String,
+    scores: Vec<Score>,
+    winner: Option<String>,
+    /// The judge's reply as received
+    text: String,
+}
+
+/// The reply format asked of the judge
+#[derive(Deserialize)]
+struct JudgeReply {
+    scores: Vec<JudgeScore>,
+    #[serde(default)]
+    winner: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JudgeScore {
+    answer: String,
+    score: f64,
+    #[serde(default)]
+    reason: String,
+}
+
+/// Ask every model at once, then show the answers and any verdict
+pub(super) async fn run(
+    vortex: VortexClient,
+    config: &Config,
+    lineup: &Lineup,
+    messages: &[Message],
+    output: OutputFormat,
+    chrome: bool,
+    raw: bool,
+) -> Result<()> {
+    let spinner = if chrome {
+        super::thinking_spinner(&format!("{} models", lineup.models.len()))
+    } else {
+        ProgressBar::hidden()
+    };
+
+    let vortex = Arc::new(vortex);
+    let tasks: Vec<_> = lineup
+        .models
+        .iter()
+        .map(|model| {
+            let vortex = Arc::clone(&vortex);
+            let model = model.clone();
+            let messages = messages.to_vec();
+            tokio::spawn(async move { vortex.complete(&model, &messages).await })
+        })
+        .collect();
+
+    let mut answers = Vec::new();
+    for (model, task) in lineup.models.iter().zip(tasks) {
+        let outcome = match task.await.context("Comparison request failed")? {
+            Ok(completion) => {
+                let record = super::record_usage(&config.usage, &completion, messages, None);
+                Ok((completion, record))
+            }
+            Err(e) => Err(format!("{:#}", e)),
+        };
+        answers.push(Answer {
+            model: model.clone(),
+            outcome,
+        });
+        spinner.set_message(format!(
+            "{} of {} models answered...",
+            answers.len(),
+            lineup.models.len()
+        ));
+    }
+    spinner.finish_and_clear();
+
+    if output == OutputFormat::Text {
+        match column_width(answers.len()) {
+            Some(width) if chrome => print_columns(&answers, width, &config.usage.currency),
+            _ => print_stacked(&answers, chrome, raw, &config.usage.currency),
+        }
+    }
+
+    let answered: Vec<&Answer> = answers.iter().filter(|a| a.outcome.is_ok()).collect();
+    let verdict = match lineup.judge {
+        Some(ref judge) if answered.len() >= 2 => {
+            let spinner = if chrome {
+                super::thinking_spinner(&format!("{} to judge", judge))
+            } else {
+                ProgressBar::hidden()
+            };
+            let result = judge_answers(config, judge, messages, &answered).await;
+            spinner.finish_and_clear();
+            let verdict = result?;
+            if verdict.scores.is_empty() {
+                ui::eprint_warning("The judge did not reply with scores; showing its reply as is");
+            }
+            Some(verdict)
+        }
+        Some(_) => {
+            ui::eprint_warning("Fewer than two models answered; skipping the judge");
+            None
+        }
+        None => None,
+    };
+
+    match output {
+        OutputFormat::Json => print_json(&answers, verdict.as_ref(), &lineup.params)?,
+        OutputFormat::Text => {
+            if let Some(ref verdict) = verdict {
+                print_verdict(verdict, chrome);
+            }
+            if chrome {
+                println!("  {}", "─".repeat(60));
+                println!();
+            }
+        }
+    }
+
+    let failed: Vec<String> = answers
+        .iter()
+        .filter_map(|a| a.outcome.as_ref().err().map(|_| a.model.clone()))
+        .collect();
+    if !failed.is_empty() {
+        bail!(
+            "{} of {} models failed: {}",
+            failed.len(),
+            answers.len(),
+            failed.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Width of each column, if the answers fit side by side
+fn column_width(count: usize) -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    let (_, columns) = console::Term::stdout().size();
+    fit_columns(columns as usize, count)
+}
+
+/// Width of `count` columns within a terminal `columns` wide, if they fit
+fn fit_columns(columns: usize, count: usize) -> Option<usize> {
+    let gaps = console::measure_text_width(GAP) * (count - 1);
+    let available = columns.saturating_sub(2 + gaps);
+    let width = (available / count).min(MAX_COLUMN);
+    (width >= MIN_COLUMN).then_some(width)
+}
+
+/// Latency, tokens and cost of an answer, on one line
+fn stats(completion: &Completion, record: &usage::Record, currency: &str) -> String {
+    let mut parts = vec![
+        format!("{:.2}s", completion.latency.as_secs_f64()),
+        format!(
+            "{}{} tokens",
+            if record.estimated { "~" } else { "" },
+            record.prompt_tokens + record.completion_tokens
+        ),
+    ];
+    if let Some(cost) = record.cost {
+        parts.push(format!("{:.4} {}", cost, currency));
+    }
+    parts.join(" · ")
+}
+
+/// Print the answers in columns, one per model
+fn print_columns(answers: &[Answer], width: usize, currency: &str) {
+    let names: Vec<Vec<String>> = answers
+        .iter()
+        .map(|a| vec![a.model.cyan().bold().to_string()])
+        .collect();
+    let rules: Vec<Vec<String>> = answers.iter().map(|_| vec!["─".repeat(width)]).collect();
+    let bodies: Vec<Vec<String>> = answers
+        .iter()
+        .map(|a| match a.outcome {
+            Ok((ref completion, _)) => wrap(&completion.text, width),
+            Err(ref e) => wrap(&format!("✖ {}", e), width)
+                .into_iter()
+                .map(|line| line.red().to_string())
+                .collect(),
+        })
+        .collect();
+    let footers: Vec<Vec<String>> = answers
+        .iter()
+        .map(|a| match a.outcome {
+            Ok((ref completion, ref record)) => wrap(&stats(completion, record, currency), width)
+                .into_iter()
+                .map(|line| line.dimmed().to_string())
+                .collect(),
+            Err(_) => Vec::new(),
+        })
+        .collect();
+
+    for section in [&names, &rules, &bodies, &rules, &footers] {
+        for row in columns(section, width) {
+            println!("  {}", row.trim_end());
+        }
+    }
+    println!();
+}
+
+/// Print the answers one after another
+fn print_stacked(answers: &[Answer], chrome: bool, raw: bool, currency: &str) {
+    for answer in answers {
+        if chrome {
+            println!(
+                "  {} {} {}",
+                "──".dimmed(),
+                answer.model.cyan().bold(),
+                "─"
+                    .repeat(56usize.saturating_sub(answer.model.len()))
+                    .dimmed()
+            );
+            println!();
+        } else {
+            println!("== {} ==", answer.model);
+        }
+        match answer.outcome {
+            Ok((ref completion, ref record)) => {
+                if chrome {
+                    print!("  🧠 ");
+                }
+                let mut printer = ResponsePrinter::new(raw || !chrome);
+                printer.write(&completion.text);
+                printer.finish();
+                if chrome {
+                    println!();
+                    println!("  {}", stats(completion, record, currency).dimmed());
+                }
+            }
+            Err(ref e) => println!("  {} {}", "✖".red().bold(), e),
+        }
+        println!();
+    }
+}
+
+/// Print the judge's scores, or its reply when it could not be read
+fn print_verdict(verdict: &Verdict, chrome: bool) {
+    if chrome {
+        println!("  {} {}", "⚖ Judge:".cyan().bold(), verdict.model);
+    } else {
+        println!("== judge: {} ==", verdict.model);
+    }
+    if verdict.scores.is_empty() {
+        for line in verdict.text.trim().lines() {
+            println!("    {}", line);
+        }
+        println!();
+        return;
+    }
+
+    let width = verdict
+        .scores
+        .iter()
+        .map(|s| s.model.len())
+        .max()
+        .unwrap_or(0);
+    for score in &verdict.scores {
+        println!(
+            "    {:<width$}  {:>2}/10  {}",
+            score.model,
+            score.score,
+            score.reason.dimmed(),
+            width = width
+        );
+    }
+    if let Some(ref winner) = verdict.winner {
+        println!("    {} {}", "Winner:".green().bold(), winner);
+    }
+    println!();
+}
+
+/// Ask the judge to score the answers, hiding which model gave which
+async fn judge_answers(
+    config: &Config,
+    judge: &str,
+    messages: &[Message],
+    answers: &[&Answer],
+) -> Result<Verdict> {
+    let question = messages
+        .iter()
+        .rev()
+        .find(|m| m.role == "user")
+        .map(|m| m.content.as_str())
+        .unwrap_or_default();
+    let mut prompt = format!("Question:\n{}\n", question);
+    for (i, answer) in answers.iter().enumerate() {
+        if let Ok((ref completion, _)) = answer.outcome {
+            prompt.push_str(&format!("\nAnswer {}:\n{}\n", label(i), completion.text));
+        }
+    }
+
+    // The judge scores with its own settings, so --max-tokens or --stop
+    // meant for the answers cannot cut its reply short
+    let client = VortexClient::new(&config.vortex)?.with_params(Params {
+        temperature: Some(0.0),
+        ..Params::default()
+    });
+    let judging = [Message::system(JUDGE_PROMPT), Message::user(prompt)];
+    let completion = client
+        .complete(judge, &judging)
+        .await
+        .with_context(|| format!("The judge ({}) failed", judge))?;
+    super::record_usage(&config.usage, &completion, &judging, None);
+
+    let models: Vec<&str> = answers.iter().map(|a| a.model.as_str()).collect();
+    Ok(read_verdict(judge, &completion.text, &models))
+}
+
+/// Map the judge's reply back from answer labels to models
+fn read_verdict(judge: &str, text: &str, models: &[&str]) -> Verdict {
+    let model_for = |answer: &str| {
+        let answer = answer.trim().trim_start_matches("Answer").trim();
+        (0..models.len())
+            .find(|&i| label(i).to_string().eq_ignore_ascii_case(answer))
+            .map(|i| models[i].to_string())
+    };
+
+    // Models often wrap JSON in a code fence or a sentence
+    let json = match (text.find('{'), text.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &text[start..=end],
+        _ => "",
+    };
+    let (scores, winner) = match serde_json::from_str::<JudgeReply>(json) {
+        Ok(reply) => {
+            let scores = reply
+                .scores
+                .into_iter()
+                .filter_map(|s| {
+                    Some(Score {
+                        model: model_for(&s.answer)?,
+                        score: s.score,
+                        reason: s.reason,
+                    })
+                })
+                .collect();
+            (scores, reply.winner.as_deref().and_then(model_for))
+        }
+        Err(_) => (Vec::new(), None),
+    };
+
+    Verdict {
+        model: judge.to_string(),
+        scores,
+        winner,
+        text: text.to_string(),
+    }
+}
+
+/// Print the comparison as one JSON object for scripts
+fn print_json(answers: &[Answer], verdict: Option<&Verdict>, params: &Params) -> Result<()> {
+    let responses: Vec<serde_json::Value> = answers
+        .iter()
+        .map(|answer| match answer.outcome {
+            Ok((ref completion, ref record)) => serde_json::json!({
+                "model": answer.model,
+                "text": completion.text,
+                "finish_reason": completion.finish_reason,
+                "request_id": completion.id,
+                "usage": {
+                    "prompt_tokens": record.prompt_tokens,
+                    "completion_tokens": record.completion_tokens,
+                    "estimated": record.estimated,
+                },
+                "cost": record.cost,
+                "latency_ms": completion.latency.as_millis() as u64,
+                "error": null,
+            }),
+            Err(ref e) => serde_json::json!({ "model": answer.model, "error": e }),
+        })
+        .collect();
+    let output = serde_json::json!({
+        "responses": responses,
+        "judge": verdict,
+        "params": params,
+    });
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+/// "A", "B", ... for the answer at `index`
+fn label(index: usize) -> char {
+    (b'A' + index as u8) as char
+}
+
+/// Wrap text to a column, keeping its line breaks
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    textwrap::wrap(text.trim(), width)
+        .into_iter()
+        .map(|line| line.into_owned())
+        .collect()
+}
+
+/// Lay out one section of every column as rows of text
+fn columns(cells: &[Vec<String>], width: usize) -> Vec<String> {
+    let height = cells.iter().map(Vec::len).max().unwrap_or(0);
+    (0..height)
+        .map(|row| {
+            cells
+                .iter()
+                .map(|lines| {
+                    let line = lines.get(row).map(String::as_str).unwrap_or("");
+                    pad_str(line, width, Alignment::Left, None).into_owned()
+                })
+                .collect::<Vec<_>>()
+                .join(GAP)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_pad_shorter_answers() {
+        let cells = vec![wrap("one two three four", 9), vec!["short".to_string()]];
+        let rows = columns(&cells, 9);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], "one two   │ short    ");
+        assert_eq!(rows[2], "four      │          ");
+    }
+
+    #[test]
+    fn test_column_fit_counts_gap_display_width() {
+        // 2 + 30 + 3 + 30 columns: the gap is 3 cells wide, not 5 bytes
+        assert_eq!(fit_columns(65, 2), Some(30));
+        assert_eq!(fit_columns(64, 2), None);
+        assert_eq!(fit_columns(200, 2), Some(MAX_COLUMN));
+        assert_eq!(label(MAX_MODELS - 1), 'Z');
+    }
+
+    #[test]
+    fn test_verdict_maps_labels_to_models() {
+        let reply = "Here you go:\n```json\n{\"scores\": [\
+            {\"answer\": \"A\", \"score\": 7, \"reason\": \"Vague\"},\
+            {\"answer\": \"Answer B\", \"score\": 9, \"reason\": \"Precise\"},\
+            {\"answer\": \"C\", \"score\": 1}], \"winner\": \"b\"}\n```";
+        let verdict = read_verdict("judge", reply, &["vortex-v3", "vortex-v2"]);
+        assert_eq!(verdict.scores.len(), 2);
+        assert_eq!(verdict.scores[0].model, "vortex-v3");
+        assert_eq!(verdict.scores[1].score, 9.0);
+        assert_eq!(verdict.winner.as_deref(), Some("vortex-v2"));
+
+        let unreadable = read_verdict("judge", "B is better", &["vortex-v3", "vortex-v2"]);
+        assert!(unreadable.scores.is_empty());
+        assert_eq!(unreadable.text, "B is better");
+    }
+}
@@ -0,0 +1,78 @@
+//! Live cluster context
+//!
+//! `--with-status` checks every service and `--with-deploys` reads the
+//! deployment history; both are attached to the query as JSON so answers
+//! reflect the current state of the cluster rather than guesses.
+
+use super::attach::Attachment;
+use crate::commands::{deploy, status};
+use crate::utils::duration;
+use anyhow::Result;
+use chrono::Utc;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde_json::json;
+use std::time::Duration;
+
+/// Check every service and attach the results
+pub(super) async fn status(context: &str, progress: bool) -> Result<Attachment> {
+    let spinner = if progress {
+        ProgressBar::new_spinner()
+    } else {
+        ProgressBar::hidden()
+    };
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.cyan} {msg}")
+            .unwrap()
+            .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
+    );
+    spinner.enable_steady_tick(Duration::from_millis(80));
+    spinner.set_message("Checking services...");
+    let services = status::snapshot().await;
+    spinner.finish_and_clear();
+    let services = services?;
+
+    let online = services.iter().filter(|s| s.up).count();
+    let report = json!({
+        "checked_at": Utc::now().to_rfc3339(),
+        "context": context,
+        "online": online,
+        "total": services.len(),
+        "services": services,
+    });
+    Ok(attachment(
+        "status",
+        "Current service health from `titan status` (JSON)".to_string(),
+        &report,
+    ))
+}
+
+/// Attach the deployments made in `context` within `window`
+pub(super) fn deploys(window: Duration, context: &str) -> Result<Attachment> {
+    let since = Utc::now() - chrono::Duration::from_std(window)?;
+    let deployments = deploy::history::recent(since, context)?;
+    let report = json!({
+        "since": since.to_rfc3339(),
+        "context": context,
+        "deployments": deployments,
+    });
+    Ok(attachment(
+        "deploys",
+        format!(
+            "Deployments in the last {}, most recent first (JSON)",
+            duration::humanize(window)
+        ),
+        &report,
+    ))
+}
+
+fn attachment(label: &str, heading: String, report: &serde_json::Value) -> Attachment {
+    let content = serde_json::to_string_pretty(report).unwrap_or_default();
+    Attachment {
+        label: label.to_string(),
+        heading,
+        original_size: content.len(),
+        content,
+        truncated: false,
+    }
+}
@@ -0,0 +1,127 @@
+//! Explanations of failed commands (`titan explain-last`)
+//!
+//! Sends the failure recorded in `~/.titan/last_error.json` to VORTEX and
+//! streams back likely causes and fixes. The record is shown first, so it
+//! is clear what leaves the machine.
+
+use super::output::ResponsePrinter;
+use super::ResponseBlock;
+use crate::utils::config::Config;
+use crate::utils::last_error::{self, Failure};
+use crate::utils::models;
+use crate::utils::session;
+use crate::utils::ui;
+use crate::utils::vortex::{Message, VortexClient};
+use anyhow::{bail, Result};
+use colored::Colorize;
+use indicatif::ProgressBar;
+
+/// Instructions sent along with the failure
+const EXPLAIN_PROMPT: &str = "You help operators of the Titan Protocol CLI (`titan`) \
+diagnose failed commands. Given the command line, its error chain (outermost first) and any \
+HTTP responses, explain the most likely causes and concrete fixes: commands to run, \
+settings in ~/.titan/config.toml, or services to check. Be brief. Answer in Markdown with \
+a \"Likely causes\" list and a \"Fixes\" list, most likely first.";
+
+/// Explain the last recorded failure, or only show it with `show`
+pub async fn explain_last(
+    model: Option<String>,
+    show: bool,
+    raw: bool,
+    verbose: bool,
+) -> Result<()> {
+    let Some(failure) = last_error::load()? else {
+        ui::print_info("No failed titan command recorded yet");
+        return Ok(());
+    };
+
+    println!();
+    ui::print_header("EXPLAIN LAST ERROR");
+    println!();
+    print_failure(&failure);
+    if show {
+        return Ok(());
+    }
+
+    let config = Config::load()?;
+    let vortex = VortexClient::new(&config.vortex)?;
+    let model = match model {
+        Some(ref model) => {
+            let model = models::resolve_alias(model, &config.vortex.aliases);
+            super::check_model(&vortex, &config, &model, verbose).await?;
+            model
+        }
+        None => super::default_model(&config),
+    };
+    super::check_budget(&config.usage, None)?;
+
+    let messages = [
+        Message::system(EXPLAIN_PROMPT),
+        Message::user(serde_json::to_string_pretty(&failure)?),
+    ];
+    let spinner = super::thinking_spinner(&model);
+    let mut printer = ResponsePrinter::new(raw);
+    let mut block = ResponseBlock {
+        chrome: true,
+        opened: false,
+    };
+    let completion = super::request(
+        &vortex,
+        &model,
+        &messages,
+        true,
+        &spinner,
+        &mut printer,
+        &mut block,
+    )
+    .await?;
+    block.open(&ProgressBar::hidden());
+    printer.finish();
+
+    let record = super::record_usage(&config.usage, &completion, &messages, None);
+    println!();
+    println!("  {}", "─".repeat(60));
+    if verbose {
+        super::print_metadata(&completion, &record, &config.usage, None);
+    }
+    println!();
+
+    if let Some(reason) = completion.interrupted {
+        bail!("VORTEX stream interrupted: {}", reason);
+    }
+    Ok(())
+}
+
+/// Print the recorded failure, as it will be sent
+fn print_failure(failure: &Failure) {
+    println!(
+        "  {} {}",
+        "Command:".cyan().bold(),
+        failure.command.join(" ")
+    );
+    println!(
+        "  {} {}",
+        "Failed:".dimmed(),
+        session::format_time(&failure.timestamp)
+    );
+    for (i, error) in failure.errors.iter().enumerate() {
+        let label = if i == 0 { "Error:" } else { "Caused by:" };
+        println!("  {} {}", label.dimmed(), error);
+    }
+    for response in &failure.responses {
+        let request = response
+            .request_id
+            .as_deref()
+            .map(|id| format!(" (request {})", id))
+            .unwrap_or_default();
+        println!(
+            "  {} {} {} → {}{}",
+            "HTTP:".dimmed(),
+            response.method,
+            response.url,
+            response.status,
+            request
+        );
+    }
+    println!();
+}
@@ -0,0 +1,788 @@
+//! Ask command implementation
+//!
+//! Sends queries to the VORTEX AI Engine configured under `[vortex]`
+//! and displays streaming or complete responses with beautiful formatting.
+
+mod attach;
+mod batch;
+mod compare;
+mod context;
+mod explain;
+mod output;
+mod repl;
+mod tools;
+
+use crate::utils::cache;
+use crate::utils::config::{AskConfig, BudgetAction, CacheConfig, Config, UsageConfig};
+use crate::utils::duration;
+use crate::utils::models;
+use crate::utils::session::{self, Session};
+use crate::utils::templates;
+use crate::utils::ui;
+use crate::utils::usage;
+use crate::utils::vortex::{Completion, Message, ModelInfo, Params, VortexClient};
+use anyhow::{bail, Context, Result};
+pub use batch::BatchOptions;
+use colored::Colorize;
+pub use explain::explain_last;
+use indicatif::{ProgressBar, ProgressStyle};
+pub use output::OutputFormat;
+use output::ResponsePrinter;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Model used when neither `--model`, a session nor `[ask] model` names one
+pub const DEFAULT_MODEL: &str = "vortex-v3";
+
+/// The model queries go to unless one is named: `[ask] model`, which may
+/// be an alias, or `DEFAULT_MODEL`
+pub fn default_model(config: &Config) -> String {
+    let name = config.ask.model.as_deref().unwrap_or(DEFAULT_MODEL);
+    models::resolve_alias(name, &config.vortex.aliases)
+}
+
+/// Options accepted by the ask command
+pub struct AskOptions {
+    /// The query to send; absent in interactive mode
+    pub query: Option<String>,
+    /// Print tokens as they arrive
+    pub stream: bool,
+    /// Model to query, overriding the session's model
+    pub model: Option<String>,
+    /// Start a multi-turn chat instead of a single query
+    pub interactive: bool,
+    /// Continue (or start) a stored conversation
+    pub session: Option<String>,
+    /// Files sent as context along with the query
+    pub files: Vec<String>,
+    /// Attach stdin when it is piped
+    pub read_stdin: bool,
+    /// Print replies as plain text instead of rendered Markdown
+    pub raw: bool,
+    /// Prompt template to fill in and send
+    pub template: Option<String>,
+    /// Values for the template's placeholders
+    pub vars: Vec<(String, String)>,
+    /// Print only the reply, without header, spinner or separators
+    pub quiet: bool,
+    /// Print the reply as text or as a JSON object
+    pub output: OutputFormat,
+    /// Attach the current health of every service
+    pub with_status: bool,
+    /// Attach recent deployments
+    pub with_deploys: bool,
+    /// Let VORTEX call read-only titan tools while answering
+    pub tools: bool,
+    /// Sampling settings; unset ones fall back to `[ask]`
+    pub params: Params,
+    /// System prompt, replacing the session's
+    pub system: Option<String>,
+    /// File to read the system prompt from
+    pub system_file: Option<PathBuf>,
+    /// Run every prompt of a JSONL file instead of a single query
+    pub batch: Option<BatchOptions>,
+    /// Models to send the same query to, side by side
+    pub compare: Vec<String>,
+    /// Model that scores the compared answers
+    pub judge: Option<String>,
+    /// Always ask VORTEX, even with `[cache] enabled`
+    pub no_cache: bool,
+}
+
+/// Execute the ask command
+pub async fn execute(opts: AskOptions, verbose: bool) -> Result<()> {
+    let config = Config::load()?;
+    let use_tools = opts.tools || config.ask.tools.enabled;
+    let params = merge_params(&opts.params, &config.ask);
+    let mut vortex = VortexClient::new(&config.vortex)?.with_params(params.clone());
+    if use_tools {
+        vortex = vortex.with_tools(tools::definitions());
+    }
+
+    // Templates are filled in first, so a missing variable costs no request
+    let template = match opts.template {
+        Some(ref name) => {
+            let template = templates::load(name)?;
+            let vars: BTreeMap<String, String> = opts.vars.iter().cloned().collect();
+            let text = template.render(&vars)?;
+            Some((template, text))
+        }
+        None => None,
+    };
+
+    // Resume the named session, or start a throwaway one
+    let stored = match opts.session {
+        Some(ref id) => session::load(id)?,
+        None => None,
+    };
+    let is_new = stored.is_none();
+    let requested = opts
+        .model
+        .as_deref()
+        .or(template.as_ref().and_then(|(t, _)| t.model.as_deref()))
+        .map(|m| models::resolve_alias(m, &config.vortex.aliases));
+    let mut session = stored.unwrap_or_else(|| {
+        Session::new(
+            opts.session.as_deref().unwrap_or("chat"),
+            &requested.clone().unwrap_or_else(|| default_model(&config)),
+        )
+    });
+    // Compared models are each checked against their own limits
+    let lineup = if opts.compare.is_empty() {
+        None
+    } else {
+        let judge = opts.judge.as_deref();
+        Some(
+            compare::Lineup::check(&vortex, &config, &opts.compare, judge, &params, verbose)
+                .await?,
+        )
+    };
+    let model_info = match requested {
+        _ if lineup.is_some() => None,
+        Some(model) => {
+            let info = check_model(&vortex, &config, &model, verbose).await?;
+            session.model = model;
+            info
+        }
+        // Output limits can only be checked against the catalog
+        None if params.max_tokens.is_some() => {
+            check_model(&vortex, &config, &session.model, verbose).await?
+        }
+        None => None,
+    };
+    params.validate(model_info.as_ref())?;
+
+    // A given system prompt replaces the session's; the default only fills in
+    match system_prompt(opts.system, opts.system_file.as_ref())? {
+        Some(system) => session.system = Some(system),
+        None if session.system.is_none() => {
+            session.system =
+                system_prompt(config.ask.system.clone(), config.ask.system_file.as_ref())?;
+        }
+        None => {}
+    }
+    let persist = opts.session.is_some();
+
+    if let Some(ref batch) = opts.batch {
+        let (model, system) = (&session.model, session.system.as_deref());
+        return batch::run(vortex, &config, model, system, &params, batch, !opts.quiet).await;
+    }
+
+    if opts.interactive {
+        return repl::run(
+            &vortex, session, persist, &config, opts.raw, use_tools, verbose,
+        )
+        .await;
+    }
+
+    // Piped stdin and --file attachments travel with the query
+    let mut budget = ContextBudget::new(&config.ask);
+    let mut attachments = collect_attachments(&opts.files, opts.read_stdin, &mut budget)?;
+    let query = opts.query.as_deref().unwrap_or_default();
+    let text = match template {
+        Some((_, ref filled)) if query.trim().is_empty() => filled.clone(),
+        Some((_, ref filled)) => format!("{}\n\n{}", filled, query.trim()),
+        None => query.to_string(),
+    };
+    if text.trim().is_empty() && attachments.is_empty() {
+        bail!("Missing query: pass it as an argument or pipe text on stdin");
+    }
+
+    let session_id = persist.then_some(session.id.as_str());
+    check_budget(&config.usage, session_id)?;
+
+    // Scripts get the reply alone on stdout; warnings go to stderr
+    let chrome = !opts.quiet && opts.output == OutputFormat::Text;
+
+    // Live cluster state is gathered last, once the query is sure to be sent,
+    // and shares the context budget with the files
+    if opts.with_status {
+        let limit = budget.limit()?;
+        let status = context::status(&config.context(), chrome).await?;
+        attachments.push(budget.charge(status.truncate(limit)));
+    }
+    if opts.with_deploys {
+        let limit = budget.limit()?;
+        let deploys = context::deploys(config.ask.deploy_window, &config.context())?;
+        attachments.push(budget.charge(deploys.truncate(limit)));
+    }
+    let prompt = attach::compose(&text, &attachments);
+
+    let stream = opts.stream;
+    let model = session.model.clone();
+    let model = model.as_str();
+    let mut messages = session.messages();
+    messages.push(Message::user(prompt.as_str()));
+    match lineup {
+        Some(ref lineup) => lineup.check_context(&messages),
+        None => check_context(&messages, &params, model_info.as_ref()),
+    }
+    if chrome {
+        println!();
+        ui::print_header("VORTEX AI ENGINE");
+        println!();
+    }
+
+    if chrome {
+        let compared = lineup.as_ref().map(|l| l.models.join(" vs "));
+        print_request(
+            query,
+            template.as_ref().map(|(t, _)| (t, opts.vars.as_slice())),
+            compared.as_deref().unwrap_or(model),
+            &attachments,
+            persist.then_some((&session, is_new)),
+            &params,
+            stream,
+        );
+    }
+
+    if let Some(ref lineup) = lineup {
+        let (output, raw) = (opts.output, opts.raw);
+        return compare::run(vortex, &config, lineup, &messages, output, chrome, raw).await;
+    }
+
+    let mut printer = match opts.output {
+        OutputFormat::Json => ResponsePrinter::silent(),
+        OutputFormat::Text => ResponsePrinter::new(opts.raw || !chrome),
+    };
+    let mut block = ResponseBlock {
+        chrome,
+        opened: false,
+    };
+    let mut toolbox = use_tools.then(|| tools::Toolbox::new(&config.ask));
+    let mut tool_calls = Vec::new();
+
+    // Tool results reflect live state, so those answers are never cached
+    let use_cache = config.cache.enabled && !opts.no_cache && !use_tools;
+    let cache_key = use_cache.then(|| {
+        let context = config.context();
+        cache::key(&config.vortex.url, &context, model, &params, &messages)
+    });
+    let started = Instant::now();
+    let hit = cache_key
+        .as_deref()
+        .and_then(|key| cached_reply(&config.cache, key));
+
+    // A cache hit needs no request; otherwise tool calls are answered and
+    // sent back until VORTEX replies with text
+    let completion = if let Some(ref entry) = hit {
+        let completion = entry.completion(started.elapsed());
+        block.open(&ProgressBar::hidden());
+        printer.write(&completion.text);
+        completion
+    } else {
+        loop {
+            let spinner = if chrome {
+                thinking_spinner(model)
+            } else {
+                ProgressBar::hidden()
+            };
+            let completion = request(
+                &vortex,
+                model,
+                &messages,
+                stream,
+                &spinner,
+                &mut printer,
+                &mut block,
+            )
+            .await?;
+
+            let toolbox = match toolbox.as_mut() {
+                Some(toolbox)
+                    if !completion.tool_calls.is_empty() && completion.interrupted.is_none() =>
+                {
+                    toolbox
+                }
+                _ => break completion,
+            };
+            record_usage(&config.usage, &completion, &messages, session_id);
+            tool_calls.extend(toolbox.answer(&completion, &mut messages).await?);
+        }
+    };
+    block.open(&ProgressBar::hidden());
+    printer.finish();
+
+    // A cached answer costs nothing and is left out of the ledger
+    let record = match hit {
+        Some(_) => usage::Record {
+            cost: Some(0.0),
+            ..usage::Record::new(&completion, &messages, session_id, &config.usage)
+        },
+        None => {
+            if let Some(ref key) = cache_key {
+                store_reply(&config.cache, key, &completion);
+            }
+            record_usage(&config.usage, &completion, &messages, session_id)
+        }
+    };
+
+    match opts.output {
+        OutputFormat::Json => print_json(
+            &completion,
+            &record,
+            session_id,
+            &params,
+            &tool_calls,
+            hit.is_some(),
+        )?,
+        OutputFormat::Text if chrome => {
+            println!();
+            println!("  {}", "─".repeat(60));
+            if verbose {
+                print_metadata(&completion, &record, &config.usage, hit.as_ref());
+            }
+            println!();
+        }
+        OutputFormat::Text => {}
+    }
+
+    // Text already received stays on screen; the failure is still reported
+    if let Some(reason) = completion.interrupted {
+        bail!("VORTEX stream interrupted: {}", reason);
+    }
+
+    if persist {
+        session.push(Message::user(prompt));
+        session.push(Message::assistant(completion.text));
+        session::save(&session)?;
+    }
+
+    Ok(())
+}
+
+/// Print the query details shown above the reply
+fn print_request(
+    query: &str,
+    template: Option<(&templates::Template, &[(String, String)])>,
+    model: &str,
+    attachments: &[attach::Attachment],
+    session: Option<(&Session, bool)>,
+    params: &Params,
+    stream: bool,
+) {
+    if let Some((template, vars)) = template {
+        let vars: Vec<String> = vars.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        if vars.is_empty() {
+            println!("  {} {}", "Template:".cyan().bold(), template.name);
+        } else {
+            println!(
+                "  {} {} ({})",
+                "Template:".cyan().bold(),
+                template.name,
+                vars.join(", ")
+            );
+        }
+    }
+    if !query.trim().is_empty() {
+        println!("  {} {}", "Query:".cyan().bold(), query);
+    } else if template.is_none() {
+        println!("  {} {}", "Query:".cyan().bold(), "(from stdin)".dimmed());
+    }
+    println!("  {} {}", "Model:".dimmed(), model);
+    let settings = params.describe();
+    if !settings.is_empty() {
+        println!("  {} {}", "Params:".dimmed(), settings.join(", "));
+    }
+    if !attachments.is_empty() {
+        let names: Vec<String> = attachments
+            .iter()
+            .map(|a| {
+                let size = ui::format_size(a.content.len() as u64);
+                if a.truncated {
+                    format!("{} ({}, truncated)", a.label, size)
+                } else {
+                    format!("{} ({})", a.label, size)
+                }
+            })
+            .collect();
+        println!("  {} {}", "Attached:".dimmed(), names.join(", "));
+    }
+    if let Some((session, is_new)) = session {
+        let status = if is_new {
+            "new".to_string()
+        } else {
+            format!("{} earlier message(s)", session.turns.len())
+        };
+        println!("  {} {} ({})", "Session:".dimmed(), session.id, status);
+    }
+    if stream {
+        println!("  {} {}", "Mode:".dimmed(), "Streaming".yellow());
+    }
+    println!();
+}
+
+/// Spinner shown until the first part of the reply arrives
+fn thinking_spinner(model: &str) -> ProgressBar {
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.cyan} {msg}")
+            .unwrap()
+            .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
+    );
+    spinner.enable_steady_tick(Duration::from_millis(80));
+    spinner.set_message(format!("Waiting for {}...", model));
+    spinner
+}
+
+/// What is left of the attachment size limits of one query
+struct ContextBudget {
+    per_item: usize,
+    left: usize,
+    max_context_kb: usize,
+}
+
+impl ContextBudget {
+    fn new(limits: &AskConfig) -> Self {
+        Self {
+            per_item: limits.max_attachment_kb * 1024,
+            left: limits.max_context_kb * 1024,
+            max_context_kb: limits.max_context_kb,
+        }
+    }
+
+    /// Size allowed for the next attachment
+    fn limit(&self) -> Result<usize> {
+        if self.left == 0 {
+            bail!(
+                "Attachments exceed the {} KB context limit (max_context_kb)",
+                self.max_context_kb
+            );
+        }
+        Ok(self.per_item.min(self.left))
+    }
+
+    /// Count an attachment against the budget, warning if it was truncated
+    fn charge(&mut self, attachment: attach::Attachment) -> attach::Attachment {
+        if attachment.truncated {
+            ui::eprint_warning(&format!(
+                "{} is {}; sending its first and last {} only",
+                attachment.label,
+                ui::format_size(attachment.original_size as u64),
+                ui::format_size((attachment.content.len() / 2) as u64)
+            ));
+        }
+        self.left = self.left.saturating_sub(attachment.content.len());
+        attachment
+    }
+}
+
+/// Read piped stdin and every `--file`, within the configured size limits
+fn collect_attachments(
+    files: &[String],
+    read_stdin: bool,
+    budget: &mut ContextBudget,
+) -> Result<Vec<attach::Attachment>> {
+    let mut attachments = Vec::new();
+
+    // Stdin comes first, followed by the files in the order given
+    let stdin = read_stdin.then_some(None);
+    let sources = stdin
+        .into_iter()
+        .chain(files.iter().map(|path| Some(path.as_str())));
+    for source in sources {
+        let limit = budget.limit()?;
+        let attachment = match source {
+            None => match attach::from_stdin(limit)? {
+                Some(attachment) => attachment,
+                None => continue,
+            },
+            Some(path) => attach::from_file(path, limit)?,
+        };
+        attachments.push(budget.charge(attachment));
+    }
+
+    Ok(attachments)
+}
+
+/// Stop or warn once a `[usage]` budget is used up
+fn check_budget(config: &UsageConfig, session: Option<&str>) -> Result<()> {
+    let records = usage::load()?;
+    if let Some(message) = usage::over_budget(config, &records, session) {
+        if config.on_exceeded == BudgetAction::Refuse {
+            bail!("{}; query not sent (see `titan usage`)", message);
+        }
+        ui::eprint_warning(&message);
+    }
+    Ok(())
+}
+
+/// Add a completion to the usage ledger
+///
+/// A ledger that cannot be written is reported but does not fail the query.
+fn record_usage(
+    config: &UsageConfig,
+    completion: &Completion,
+    messages: &[Message],
+    session: Option<&str>,
+) -> usage::Record {
+    let record = usage::Record::new(completion, messages, session, config);
+    if let Err(e) = usage::record(&record) {
+        ui::eprint_warning(&format!("Could not record usage: {:#}", e));
+    }
+    record
+}
+
+/// Look up an answer in the response cache
+///
+/// A cache that cannot be read is reported, and the query goes to VORTEX.
+fn cached_reply(config: &CacheConfig, key: &str) -> Option<cache::Entry> {
+    cache::get(config, key).unwrap_or_else(|e| {
+        ui::eprint_warning(&format!("Could not read the response cache: {:#}", e));
+        None
+    })
+}
+
+/// Keep an answer in the response cache, within its size limit
+fn store_reply(config: &CacheConfig, key: &str, completion: &Completion) {
+    if let Err(e) = cache::put(key, completion).and_then(|_| cache::prune(config)) {
+        ui::eprint_warning(&format!("Could not update the response cache: {:#}", e));
+    }
+}
+
+/// Check a `--model` choice against the models VORTEX serves
+///
+/// An unknown model is checked again against a fresh list before failing,
+/// in case it was added since the list was cached. If the list cannot be
+/// fetched at all the query goes ahead with a warning that the model was
+/// not checked, and VORTEX reports any problem.
+async fn check_model(
+    vortex: &VortexClient,
+    config: &Config,
+    id: &str,
+    verbose: bool,
+) -> Result<Option<ModelInfo>> {
+    let mut refresh = false;
+    let model = loop {
+        let catalog = match models::catalog(vortex, &config.vortex, refresh).await {
+            Ok(catalog) => catalog,
+            Err(e) => {
+                let reason = if verbose {
+                    format!(": {:#}", e)
+                } else {
+                    String::new()
+                };
+                ui::eprint_warning(&format!(
+                    "Could not fetch the model list; {} was not checked{}",
+                    id, reason
+                ));
+                return Ok(None);
+            }
+        };
+        match models::check(id, &catalog) {
+            Ok(model) => break model.clone(),
+            Err(e) if refresh || catalog.stale => return Err(e),
+            Err(_) => refresh = true,
+        }
+    };
+
+    if model.deprecated {
+        let advice = match model.replacement {
+            Some(ref replacement) => format!("; use {} instead", replacement),
+            None => String::new(),
+        };
+        ui::eprint_warning(&format!("{} is deprecated{}", model.id, advice));
+    }
+    Ok(Some(model))
+}
+
+/// Fill in sampling settings not given on the command line from `[ask]`
+fn merge_params(flags: &Params, defaults: &AskConfig) -> Params {
+    Params {
+        temperature: flags.temperature.or(defaults.temperature),
+        top_p: flags.top_p.or(defaults.top_p),
+        max_tokens: flags.max_tokens.or(defaults.max_tokens),
+        stop: if flags.stop.is_empty() {
+            defaults.stop.clone()
+        } else {
+            flags.stop.clone()
+        },
+        seed: flags.seed.or(defaults.seed),
+    }
+}
+
+/// A system prompt given inline or in a file
+fn system_prompt(inline: Option<String>, file: Option<&PathBuf>) -> Result<Option<String>> {
+    let text = match (inline, file) {
+        (Some(text), _) => text,
+        (None, Some(path)) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read system prompt {}", path.display()))?,
+        (None, None) => return Ok(None),
+    };
+    if text.trim().is_empty() {
+        bail!("The system prompt is empty");
+    }
+    Ok(Some(text.trim().to_string()))
+}
+
+/// Warn when the prompt and the requested output may not fit the model
+fn check_context(messages: &[Message], params: &Params, model: Option<&ModelInfo>) {
+    let Some((id, context)) = model.and_then(|m| Some((&m.id, m.context_length?))) else {
+        return;
+    };
+    let prompt: u64 = messages
+        .iter()
+        .map(|m| usage::estimate_tokens(&m.content))
+        .sum();
+    let needed = prompt + params.max_tokens.unwrap_or(0);
+    if needed > context {
+        ui::eprint_warning(&format!(
+            "The prompt (~{} tokens){} may not fit the {}-token context of {}",
+            prompt,
+            params
+                .max_tokens
+                .map(|max| format!(" plus {} output tokens", max))
+                .unwrap_or_default(),
+            context,
+            id
+        ));
+    }
+}
+
+/// The block the reply is printed in, opened by its first text
+struct ResponseBlock {
+    chrome: bool,
+    opened: bool,
+}
+
+impl ResponseBlock {
+    /// Replace the spinner with the opening of the block, once
+    fn open(&mut self, spinner: &ProgressBar) {
+        spinner.finish_and_clear();
+        if self.opened {
+            return;
+        }
+        self.opened = true;
+        if self.chrome {
+            println!("  {}", "─".repeat(60));
+            println!();
+            print!("  🧠 ");
+        }
+    }
+}
+
+/// Send one request, printing any text of the reply as it arrives
+async fn request(
+    vortex: &VortexClient,
+    model: &str,
+    messages: &[Message],
+    stream: bool,
+    spinner: &ProgressBar,
+    printer: &mut ResponsePrinter,
+    block: &mut ResponseBlock,
+) -> Result<Completion> {
+    let result = if stream {
+        // The first token replaces the spinner
+        vortex
+            .complete_stream(model, messages, |token| {
+                block.open(spinner);
+                printer.write(token);
+            })
+            .await
+    } else {
+        let result = vortex.complete(model, messages).await;
+        if let Ok(ref completion) = result {
+            if !completion.text.is_empty() {
+                block.open(spinner);
+                printer.write(&completion.text);
+            }
+        }
+        result
+    };
+    spinner.finish_and_clear();
+    result
+}
+
+/// Print a completion as one JSON object for scripts
+fn print_json(
+    completion: &Completion,
+    record: &usage::Record,
+    session: Option<&str>,
+    params: &Params,
+    tool_calls: &[tools::Executed],
+    cached: bool,
+) -> Result<()> {
+    let output = serde_json::json!({
+        "text": completion.text,
+        "model": completion.model,
+        "finish_reason": completion.finish_reason,
+        "request_id": completion.id,
+        "usage": {
+            "prompt_tokens": record.prompt_tokens,
+            "completion_tokens": record.completion_tokens,
+            "estimated": record.estimated,
+        },
+        "cost": record.cost,
+        "latency_ms": completion.latency.as_millis() as u64,
+        "session": session,
+        "params": params,
+        "interrupted": completion.interrupted,
+        "tool_calls": tool_calls,
+        "cached": cached,
+    });
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+/// Print the metadata VORTEX reported for a completion
+fn print_metadata(
+    completion: &Completion,
+    record: &usage::Record,
+    config: &UsageConfig,
+    cached: Option<&cache::Entry>,
+) {
+    println!();
+    println!("  {}", "Response Metadata:".dimmed());
+    println!(
+        "    {} {}{} ({} prompt + {} completion)",
+        "Tokens:".dimmed(),
+        if record.estimated { "~" } else { "" },
+        record.prompt_tokens + record.completion_tokens,
+        record.prompt_tokens,
+        record.completion_tokens
+    );
+    match record.cost {
+        Some(cost) => println!("    {} {:.4} {}", "Cost:".dimmed(), cost, config.currency),
+        None => println!(
+            "    {} no price for {} in [usage.prices]",
+            "Cost:".dimmed(),
+            record.model
+        ),
+    }
+    println!(
+        "    {} {:.2}s",
+        "Latency:".dimmed(),
+        completion.latency.as_secs_f64()
+    );
+    if let Some(ttft) = completion.time_to_first_token {
+        let generating = completion.latency.saturating_sub(ttft).as_secs_f64();
+        println!("    {} {:.2}s", "First token:".dimmed(), ttft.as_secs_f64());
+        if generating > 0.0 {
+            println!(
+                "    {} {:.1} tokens/s",
+                "Speed:".dimmed(),
+                completion.completion_tokens() as f64 / generating
+            );
+        }
+    }
+    println!("    {} {}", "Model:".dimmed(), completion.model);
+    if let Some(ref reason) = completion.finish_reason {
+        println!("    {} {}", "Finish:".dimmed(), reason);
+    }
+    if let Some(ref id) = completion.id {
+        println!("    {} {}", "Request ID:".dimmed(), id);
+    }
+    if let Some(entry) = cached {
+        let age = (chrono::Utc::now() - entry.created)
+            .to_std()
+            .unwrap_or_default();
+        println!(
+            "    {} yes, stored {} ago (served {} time(s))",
+            "Cached:".dimmed(),
+            duration::humanize(age),
+            entry.hits
+        );
+    }
+}
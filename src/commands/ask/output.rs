@@ -0,0 +1,96 @@
+//! Response output
+//!
+//! Prints VORTEX replies raw or through the Markdown renderer, keeping
+//! rendered lines aligned under the 🧠 marker.
+
+use crate::utils::markdown::MarkdownRenderer;
+use std::io::{self, IsTerminal, Write};
+
+/// Columns taken by the "  🧠 " prefix
+const INDENT: usize = 5;
+
+/// Widest the rendered text gets, even on very wide terminals
+const MAX_WIDTH: usize = 100;
+
+/// Output formats for `titan ask --output`
+#[derive(Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// Writes a reply as it arrives
+pub(super) struct ResponsePrinter {
+    renderer: Option<MarkdownRenderer>,
+    first_line: bool,
+    /// Print nothing, when the reply is reported some other way
+    silent: bool,
+}
+
+impl ResponsePrinter {
+    /// Render Markdown unless `raw` is set or stdout is not a terminal
+    pub fn new(raw: bool) -> Self {
+        let markdown = !raw && io::stdout().is_terminal();
+        Self {
+            renderer: markdown.then(|| MarkdownRenderer::new(render_width())),
+            first_line: true,
+            silent: false,
+        }
+    }
+
+    /// A printer that discards the reply
+    pub fn silent() -> Self {
+        Self {
+            renderer: None,
+            first_line: true,
+            silent: true,
+        }
+    }
+
+    /// Print the next piece of the reply
+    pub fn write(&mut self, text: &str) {
+        if self.silent {
+            return;
+        }
+        match self.renderer.as_mut() {
+            Some(renderer) => {
+                let rendered = renderer.push(text);
+                self.print_rendered(&rendered);
+            }
+            None => print!("{}", text),
+        }
+        let _ = io::stdout().flush();
+    }
+
+    /// Print anything held back and end the reply with a newline
+    pub fn finish(&mut self) {
+        if self.silent {
+            return;
+        }
+        match self.renderer.as_mut() {
+            Some(renderer) => {
+                let rendered = renderer.finish();
+                self.print_rendered(&rendered);
+            }
+            None => println!(),
+        }
+        let _ = io::stdout().flush();
+    }
+
+    fn print_rendered(&mut self, rendered: &str) {
+        for line in rendered.split_inclusive('\n') {
+            if self.first_line {
+                self.first_line = false;
+                print!("{}", line);
+            } else {
+                print!("{}{}", " ".repeat(INDENT), line);
+            }
+        }
+    }
+}
+
+/// Width available for rendered text
+fn render_width() -> usize {
+    let (_, columns) = console::Term::stdout().size();
+    (columns as usize).min(MAX_WIDTH).saturating_sub(INDENT)
+}
@@ -0,0 +1,411 @@
+//! Interactive chat mode (`titan ask -i`)
+//!
+//! Keeps a multi-turn conversation with VORTEX, sending the whole history
+//! on every turn. With `--session` every turn is saved as it completes.
+//! A line ending in `\` continues on the next line, and text between two
+//! `"""` lines is sent as one message. Lines starting with `/` are
+//! commands. With tools enabled, approved tool calls run between the
+//! question and the reply.
+
+use super::output::ResponsePrinter;
+use super::tools::Toolbox;
+use crate::utils::config::{self, Config};
+use crate::utils::models;
+use crate::utils::session::{self, Session};
+use crate::utils::ui;
+use crate::utils::vortex::{Completion, Message, VortexClient};
+use anyhow::{Context, Result};
+use chrono::Local;
+use colored::Colorize;
+use indicatif::{ProgressBar, ProgressStyle};
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use std::time::{Duration, Instant};
+
+const PROMPT: &str = "  › ";
+const CONTINUATION: &str = "  · ";
+const BLOCK_DELIMITER: &str = "\"\"\"";
+
+/// State of one chat
+struct Chat<'a> {
+    session: Session,
+    /// Whether the session is stored under `~/.titan/sessions`
+    persist: bool,
+    config: &'a Config,
+}
+
+impl Chat<'_> {
+    /// Session ID recorded in the usage ledger, if it is a named one
+    fn session_id(&self) -> Option<&str> {
+        self.persist.then_some(self.session.id.as_str())
+    }
+
+    /// Store the session after a change, if it is a named one
+    fn save(&self) -> Result<()> {
+        if self.persist {
+            session::save(&self.session)?;
+        }
+        Ok(())
+    }
+}
+
+/// What the loop should do after a command
+enum Flow {
+    Continue,
+    Exit,
+    /// Not a known command, so the line is sent as a message
+    Send,
+}
+
+/// Run the chat loop until `/exit` or Ctrl+D
+pub(super) async fn run(
+    vortex: &VortexClient,
+    session: Session,
+    persist: bool,
+    config: &Config,
+    raw: bool,
+    tools: bool,
+    verbose: bool,
+) -> Result<()> {
+    let mut editor = DefaultEditor::new()?;
+    let history_path = config::titan_dir().join("ask_history");
+    let _ = editor.load_history(&history_path);
+
+    let mut chat = Chat {
+        session,
+        persist,
+        config,
+    };
+    // Tools the user chose to always run stay approved for the rest of the chat
+    let mut toolbox = tools.then(|| Toolbox::new(&config.ask));
+
+    println!();
+    ui::print_header("VORTEX AI ENGINE");
+    println!();
+    println!(
+        "  Chatting with {}. Type {} for commands, {} or Ctrl+D to leave.",
+        chat.session.model.cyan().bold(),
+        "/help".yellow(),
+        "/exit".yellow()
+    );
+    if chat.persist {
+        println!(
+            "  {} {} ({} earlier message(s))",
+            "Session:".dimmed(),
+            chat.session.id,
+            chat.session.turns.len()
+        );
+    }
+    println!(
+        "  {}",
+        "End a line with \\ or wrap text in \"\"\" for multi-line input.".dimmed()
+    );
+    println!();
+
+    while let Some(input) = read_input(&mut editor)? {
+        if input.trim().is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(input.as_str());
+
+        if let Some(command) = input.trim().strip_prefix('/') {
+            match handle_command(vortex, &mut chat, command, verbose).await {
+                Ok(Flow::Continue) => continue,
+                Ok(Flow::Exit) => break,
+                Ok(Flow::Send) => {}
+                Err(e) => {
+                    ui::print_error(&format!("{:#}", e));
+                    continue;
+                }
+            }
+        }
+
+        if let Err(e) = super::check_budget(&chat.config.usage, chat.session_id()) {
+            ui::print_error(&format!("{:#}", e));
+            continue;
+        }
+
+        let mut messages = chat.session.messages();
+        messages.push(Message::user(input.as_str()));
+
+        // Failed or cancelled turns are not part of the conversation
+        let reply = generate(vortex, &chat, messages, toolbox.as_mut(), raw, verbose).await;
+        if let Some(reply) = reply {
+            chat.session.push(Message::user(input));
+            chat.session.push(Message::assistant(reply));
+            if let Err(e) = chat.save() {
+                ui::print_error(&format!("{:#}", e));
+            }
+        }
+        println!();
+    }
+
+    if let Some(dir) = history_path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let _ = editor.save_history(&history_path);
+
+    println!();
+    println!("  {} Chat ended", "⏹".yellow());
+    println!();
+    Ok(())
+}
+
+/// Read one message, joining continuation lines; `None` on Ctrl+D
+fn read_input(editor: &mut DefaultEditor) -> Result<Option<String>> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut in_block = false;
+
+    loop {
+        let prompt = if lines.is_empty() && !in_block {
+            PROMPT
+        } else {
+            CONTINUATION
+        };
+
+        match editor.readline(prompt) {
+            Ok(line) if in_block => {
+                if line.trim() == BLOCK_DELIMITER {
+                    return Ok(Some(lines.join("\n")));
+                }
+                lines.push(line);
+            }
+            Ok(line) if lines.is_empty() && line.trim() == BLOCK_DELIMITER => in_block = true,
+            Ok(line) => match line.strip_suffix('\\') {
+                Some(partial) => lines.push(partial.to_string()),
+                None => {
+                    lines.push(line);
+                    return Ok(Some(lines.join("\n")));
+                }
+            },
+            // Ctrl+C discards the message being typed
+            Err(ReadlineError::Interrupted) => return Ok(Some(String::new())),
+            Err(ReadlineError::Eof) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Handle a slash command (without the leading `/`)
+async fn handle_command(
+    vortex: &VortexClient,
+    chat: &mut Chat<'_>,
+    command: &str,
+    verbose: bool,
+) -> Result<Flow> {
+    let (name, arg) = match command.split_once(char::is_whitespace) {
+        Some((name, arg)) => (name, arg.trim()),
+        None => (command, ""),
+    };
+
+    match name {
+        "exit" | "quit" | "q" => return Ok(Flow::Exit),
+        "help" | "?" => print_help(),
+        "model" if arg.is_empty() => ui::print_info(&format!("Model: {}", chat.session.model)),
+        "model" => {
+            // Checked like `--model`, so a typo fails here and not next turn
+            let model = models::resolve_alias(arg, &chat.config.vortex.aliases);
+            super::check_model(vortex, chat.config, &model, verbose).await?;
+            chat.session.model = model;
+            chat.save()?;
+            ui::print_success(&format!("Switched to {}", chat.session.model));
+        }
+        "system" if arg.is_empty() => match chat.session.system {
+            Some(ref system) => ui::print_info(&format!("System prompt: {}", system)),
+            None => ui::print_info("No system prompt set"),
+        },
+        "system" if arg == "off" => {
+            chat.session.system = None;
+            chat.save()?;
+            ui::print_success("System prompt cleared");
+        }
+        "system" => {
+            chat.session.system = Some(arg.to_string());
+            chat.save()?;
+            ui::print_success("System prompt set");
+        }
+        "reset" => {
+            chat.session.turns.clear();
+            chat.save()?;
+            ui::print_success("Conversation cleared");
+        }
+        "save" => {
+            let path = if arg.is_empty() {
+                format!("titan-chat-{}.md", Local::now().format("%Y%m%d-%H%M%S"))
+            } else {
+                arg.to_string()
+            };
+            save(&chat.session, &path)?;
+            ui::print_success(&format!(
+                "Saved {} message(s) to {}",
+                chat.session.turns.len(),
+                path
+            ));
+        }
+        // A pasted path such as `/var/log/titan.log` is part of the message
+        _ => return Ok(Flow::Send),
+    }
+
+    Ok(Flow::Continue)
+}
+
+fn print_help() {
+    let commands = [
+        ("/model [NAME]", "Show or switch the model"),
+        ("/system [TEXT|off]", "Show, set or clear the system prompt"),
+        ("/reset", "Forget the conversation so far"),
+        ("/save [FILE]", "Save the transcript (.md, or .json)"),
+        ("/exit", "Leave the chat"),
+    ];
+    for (command, description) in commands {
+        println!("  {:<22} {}", command.yellow(), description.dimmed());
+    }
+}
+
+/// Write the conversation as Markdown, or as JSON for `.json` paths
+fn save(session: &Session, path: &str) -> Result<()> {
+    let contents = if path.ends_with(".json") {
+        serde_json::to_string_pretty(session)?
+    } else {
+        session.to_markdown()
+    };
+
+    std::fs::write(path, contents).with_context(|| format!("Failed to write {}", path))
+}
+
+/// Stream a reply to the conversation, returning it unless cancelled or failed
+async fn generate(
+    vortex: &VortexClient,
+    chat: &Chat<'_>,
+    mut messages: Vec<Message>,
+    mut toolbox: Option<&mut Toolbox<'_>>,
+    raw: bool,
+    verbose: bool,
+) -> Option<String> {
+    let model = chat.session.model.as_str();
+    if let Some(ref mut toolbox) = toolbox {
+        toolbox.start_turn();
+    }
+    let mut printer = ResponsePrinter::new(raw);
+    let mut started = false;
+    // What the current request produced, for the ledger if it is cancelled
+    let mut partial = String::new();
+    let (mut chunks, mut sent): (u64, Instant);
+
+    // Tool calls are answered and sent back until VORTEX replies with text
+    let outcome = loop {
+        partial.clear();
+        chunks = 0;
+        sent = Instant::now();
+        let spinner = ProgressBar::new_spinner();
+        spinner.set_style(
+            ProgressStyle::default_spinner()
+                .template("  {spinner:.cyan} {msg}")
+                .unwrap()
+                .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
+        );
+        spinner.enable_steady_tick(Duration::from_millis(80));
+        spinner.set_message(format!("Waiting for {}...", model));
+
+        let request = vortex.complete_stream(model, &messages, |token| {
+            if !started {
+                spinner.finish_and_clear();
+                print!("  🧠 ");
+                started = true;
+            }
+            partial.push_str(token);
+            chunks += 1;
+            printer.write(token);
+        });
+
+        // Ctrl+C stops this reply only; the session carries on
+        let outcome = tokio::select! {
+            result = request => Some(result),
+            _ = tokio::signal::ctrl_c() => None,
+        };
+        spinner.finish_and_clear();
+
+        let (completion, toolbox) = match (outcome, toolbox.as_deref_mut()) {
+            (Some(Ok(completion)), Some(toolbox))
+                if !completion.tool_calls.is_empty() && completion.interrupted.is_none() =>
+            {
+                (completion, toolbox)
+            }
+            (outcome, _) => break outcome,
+        };
+        super::record_usage(
+            &chat.config.usage,
+            &completion,
+            &messages,
+            chat.session_id(),
+        );
+        if let Err(e) = toolbox.answer(&completion, &mut messages).await {
+            break Some(Err(e));
+        }
+    };
+    if started {
+        printer.finish();
+    }
+
+    match outcome {
+        None => {
+            // Tokens generated before the cancel were still used
+            let completion = Completion {
+                id: None,
+                model: model.to_string(),
+                text: partial,
+                finish_reason: None,
+                usage: None,
+                tool_calls: Vec::new(),
+                latency: sent.elapsed(),
+                time_to_first_token: None,
+                interrupted: Some("cancelled".to_string()),
+                chunks,
+            };
+            super::record_usage(
+                &chat.config.usage,
+                &completion,
+                &messages,
+                chat.session_id(),
+            );
+            println!("  {} Generation cancelled", "⏹".yellow());
+            None
+        }
+        Some(Err(e)) => {
+            ui::print_error(&format!("{:#}", e));
+            None
+        }
+        Some(Ok(completion)) => {
+            let record = super::record_usage(
+                &chat.config.usage,
+                &completion,
+                &messages,
+                chat.session_id(),
+            );
+            if let Some(reason) = completion.interrupted {
+                ui::print_warning(&format!(
+                    "Reply interrupted ({}); it was not added to the conversation",
+                    reason
+                ));
+                return None;
+            }
+            if verbose {
+                println!(
+                    "  {}",
+                    format!(
+                        "{} tokens · {:.2}s · {}{}",
+                        record.completion_tokens,
+                        completion.latency.as_secs_f64(),
+                        completion.model,
+                        record
+                            .cost
+                            .map(|cost| format!(" · {:.4} {}", cost, chat.config.usage.currency))
+                            .unwrap_or_default()
+                    )
+                    .dimmed()
+                );
+            }
+            Some(completion.text)
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! Tools VORTEX may call while answering (`titan ask --tools`)
+//!
+//! Every tool is a read-only view backed by a command module. A requested
+//! call is shown on stderr and runs once approved, at a prompt for each call
+//! or up front through `[ask.tools] allow`; its result goes back to VORTEX as
+//! a `tool` message so the answer can build on it.
+
+use crate::commands::{deploy, status};
+use crate::utils::config::{AskConfig, Config};
+use crate::utils::duration;
+use crate::utils::registry;
+use crate::utils::vortex::{Completion, Message, ToolCall};
+use anyhow::{bail, Result};
+use chrono::Utc;
+use colored::Colorize;
+use dialoguer::Select;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+
+/// Tools offered to VORTEX, with their descriptions
+pub(super) const TOOLS: &[(&str, &str)] = &[
+    (
+        "service_status",
+        "Check the current health of Titan services, as `titan status` does",
+    ),
+    (
+        "status_history",
+        "Uptime, mean latency and outages from recorded `titan status` runs",
+    ),
+    (
+        "deploy_history",
+        "Deployments recorded by `titan deploy`, most recent first",
+    ),
+];
+
+/// Chat completions `tools` entries for every tool
+pub(super) fn definitions() -> Vec<Value> {
+    let service = json!({
+        "type": "string",
+        "description": "Service name, e.g. HERMES; omit for all services",
+    });
+    let since = json!({
+        "type": "string",
+        "description": "How far back to look, e.g. 30m, 6h or 7d",
+    });
+
+    TOOLS
+        .iter()
+        .map(|(name, description)| {
+            let properties = match *name {
+                "service_status" => json!({ "service": service }),
+                _ => json!({ "service": service, "since": since }),
+            };
+            json!({
+                "type": "function",
+                "function": {
+                    "name": name,
+                    "description": description,
+                    "parameters": { "type": "object", "properties": properties },
+                },
+            })
+        })
+        .collect()
+}
+
+/// A requested call, as reported by `--output json`
+#[derive(Serialize)]
+pub(super) struct Executed {
+    pub name: String,
+    pub arguments: Value,
+    pub approved: bool,
+}
+
+/// Approves and runs the calls of one command
+pub(super) struct Toolbox<'a> {
+    config: &'a AskConfig,
+    /// Tools that run without asking: the allow list, plus any the user
+    /// chose to always run at a prompt
+    always: HashSet<String>,
+    rounds: usize,
+}
+
+impl<'a> Toolbox<'a> {
+    pub fn new(config: &'a AskConfig) -> Self {
+        Self {
+            config,
+            always: config.tools.allow.iter().cloned().collect(),
+            rounds: 0,
+        }
+    }
+
+    /// Count tool rounds afresh for the next question of a chat
+    pub fn start_turn(&mut self) {
+        self.rounds = 0;
+    }
+
+    /// Run the calls a completion asked for and append the exchange to
+    /// `messages`, ready for the next request
+    pub async fn answer(
+        &mut self,
+        completion: &Completion,
+        messages: &mut Vec<Message>,
+    ) -> Result<Vec<Executed>> {
+        self.rounds += 1;
+        if self.rounds > self.config.tools.max_rounds {
+            bail!(
+                "VORTEX asked for tools {} times without answering (raise [ask.tools] max_rounds)",
+                self.config.tools.max_rounds
+            );
+        }
+
+        let mut reply = Message::assistant(completion.text.as_str());
+        reply.tool_calls = completion.tool_calls.clone();
+        messages.push(reply);
+
+        let mut executed = Vec::new();
+        for call in &completion.tool_calls {
+            let arguments: Value = match call.function.arguments.trim() {
+                "" => json!({}),
+                text => serde_json::from_str(text).unwrap_or(Value::Null),
+            };
+            let known = TOOLS.iter().any(|(name, _)| *name == call.function.name);
+            let approved = known && self.approve(call, &arguments)?;
+            let result = if approved {
+                run(&call.function.name, &arguments, self.config.deploy_window).await
+            } else if known {
+                json!({ "error": "The user declined to run this tool" })
+            } else {
+                json!({ "error": format!("Unknown tool '{}'", call.function.name) })
+            };
+            messages.push(Message::tool(&call.id, result.to_string()));
+            executed.push(Executed {
+                name: call.function.name.clone(),
+                arguments,
+                approved,
+            });
+        }
+        eprintln!();
+        Ok(executed)
+    }
+
+    /// Show a call and decide whether it may run
+    fn approve(&mut self, call: &ToolCall, arguments: &Value) -> Result<bool> {
+        let name = call.function.name.as_str();
+        eprintln!(
+            "  🔧 VORTEX wants to run {} {}",
+            name.cyan().bold(),
+            arguments.to_string().dimmed()
+        );
+
+        if self.always.contains(name) {
+            return Ok(true);
+        }
+        if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+            eprintln!(
+                "  {} Declined: {} is not in [ask.tools] allow and there is no terminal to ask",
+                "⚠".yellow().bold(),
+                name
+            );
+            return Ok(false);
+        }
+
+        let always = format!("Yes, and run {} without asking from now on", name);
+        let choice = Select::new()
+            .with_prompt(format!("  Run {}?", name))
+            .items(&["No", "Yes", always.as_str()])
+            .default(0)
+            .interact()?;
+        if choice == 2 {
+            self.always.insert(name.to_string());
+        }
+        Ok(choice > 0)
+    }
+}
+
+/// Run a tool, describing any failure in the result VORTEX receives
+async fn run(name: &str, arguments: &Value, deploy_window: Duration) -> Value {
+    let service = arguments.get("service").and_then(Value::as_str);
+    let since = |default: Duration| match arguments.get("since").and_then(Value::as_str) {
+        Some(text) => duration::parse(text).map_err(anyhow::Error::msg),
+        None => Ok(default),
+    };
+
+    let result: Result<Value> = async {
+        match name {
+            "service_status" => {
+                let wanted = service.map(registry::resolve).transpose()?;
+                let services: Vec<status::ServiceHealth> = status::snapshot()
+                    .await?
+                    .into_iter()
+                    .filter(|s| wanted.map_or(true, |w| w.name == s.service))
+                    .collect();
+                Ok(json!({ "checked_at": Utc::now().to_rfc3339(), "services": services }))
+            }
+            "status_history" => {
+                let since = since(Duration::from_secs(24 * 3600))?;
+                let uptime = status::uptime(service, since)?;
+                Ok(json!({ "window": duration::humanize(since), "services": uptime }))
+            }
+            "deploy_history" => {
+                let since = since(deploy_window)?;
+                let wanted = service.map(registry::resolve).transpose()?;
+                let cutoff = Utc::now() - chrono::Duration::from_std(since)?;
+                let context = Config::load()?.context();
+                let deployments: Vec<deploy::history::Record> =
+                    deploy::history::recent(cutoff, &context)?
+                        .into_iter()
+                        .filter(|d| wanted.map_or(true, |w| w.name == d.service))
+                        .collect();
+                Ok(json!({ "window": duration::humanize(since), "deployments": deployments }))
+            }
+            _ => bail!("Unknown tool '{}'", name),
+        }
+    }
+    .await;
+
+    result.unwrap_or_else(|e| json!({ "error": format!("{:#}", e) }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definitions_cover_every_tool() {
+        let definitions = definitions();
+        assert_eq!(definitions.len(), TOOLS.len());
+        assert_eq!(definitions[0]["function"]["name"], "service_status");
+        assert!(definitions[0]["function"]["parameters"]["properties"]
+            .get("since")
+            .is_none());
+        assert!(definitions[2]["function"]["parameters"]["properties"]["since"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_bad_arguments_are_reported_to_vortex() {
+        let window = Duration::from_secs(3600);
+        let unknown = run("delete_everything", &json!({}), window).await;
+        assert_eq!(unknown["error"], "Unknown tool 'delete_everything'");
+
+        let bad = run("deploy_history", &json!({ "since": "soon" }), window).await;
+        assert!(bad["error"].is_string(), "{}", bad);
+    }
+}
@@ -0,0 +1,80 @@
+//! Cache command implementation
+//!
+//! Reports on and clears the VORTEX response cache kept by `titan ask`
+//! when `[cache] enabled` is set.
+
+use crate::utils::config::Config;
+use crate::utils::{cache, duration, ui};
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+use colored::Colorize;
+
+/// Print entries, size, hits and the settings in effect
+pub fn stats() -> Result<()> {
+    let config = Config::load()?.cache;
+    let stats = cache::stats(&config)?;
+
+    println!();
+    ui::print_header("VORTEX RESPONSE CACHE");
+    println!();
+    if config.enabled {
+        println!("  {} {}", "Status:".dimmed(), "enabled".green());
+    } else {
+        println!(
+            "  {} {} (set [cache] enabled = true to use it)",
+            "Status:".dimmed(),
+            "disabled".yellow()
+        );
+    }
+    println!("  {} {}", "Location:".dimmed(), cache::dir().display());
+    println!(
+        "  {} {}, up to {} MB",
+        "TTL:".dimmed(),
+        duration::humanize(config.ttl),
+        config.max_size_mb
+    );
+    println!();
+
+    if stats.entries == 0 {
+        println!("  {} No cached responses", "○".dimmed());
+        println!();
+        return Ok(());
+    }
+
+    let expired = if stats.expired > 0 {
+        format!(" ({} expired)", stats.expired)
+    } else {
+        String::new()
+    };
+    println!("  {} {}{}", "Entries:".dimmed(), stats.entries, expired);
+    println!("  {} {}", "Size:".dimmed(), ui::format_size(stats.bytes));
+    println!(
+        "  {} {} (~{} completion tokens not generated again)",
+        "Hits:".dimmed(),
+        stats.hits,
+        stats.saved_tokens
+    );
+    if let (Some(oldest), Some(newest)) = (stats.oldest, stats.newest) {
+        println!("  {} {}", "Oldest:".dimmed(), format_time(&oldest));
+        println!("  {} {}", "Newest:".dimmed(), format_time(&newest));
+    }
+    println!();
+    Ok(())
+}
+
+/// Delete every cached response
+pub fn clear() -> Result<()> {
+    let removed = cache::clear()?;
+    if removed == 0 {
+        ui::print_info("The response cache is already empty");
+    } else {
+        ui::print_success(&format!("Removed {} cached response(s)", removed));
+    }
+    Ok(())
+}
+
+fn format_time(time: &DateTime<Utc>) -> String {
+    time.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
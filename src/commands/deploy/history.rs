@@ -0,0 +1,73 @@
+//! Deployment history
+//!
+//! Every completed `titan deploy` appends one record per deployed service to
+//! `~/.titan/deploy-history.jsonl`, so later commands can tell what changed
+//! and when. The file is rotated like the status history.
+
+use crate::utils::{config, rotate};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+/// Rotate the history file once it grows past this size
+const MAX_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Number of rotated files kept next to the active one
+const GENERATIONS: usize = 3;
+
+/// A deployment of one service as stored on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record {
+    pub ts: DateTime<Utc>,
+    pub service: String,
+    pub env: String,
+    pub image: String,
+    /// Context the CLI was pointed at when deploying
+    pub context: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+fn history_path() -> PathBuf {
+    config::titan_dir().join("deploy-history.jsonl")
+}
+
+/// Append a completed deployment to the history file
+pub(super) fn record(records: &[Record]) -> Result<()> {
+    let path = history_path();
+    std::fs::create_dir_all(config::titan_dir())?;
+    rotate::rotate_if_needed(&path, MAX_BYTES, GENERATIONS)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    for record in records {
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+    }
+    Ok(())
+}
+
+/// Deployments made in `context` since `since`, most recent first
+pub fn recent(since: DateTime<Utc>, context: &str) -> Result<Vec<Record>> {
+    let mut records = Vec::new();
+    for path in rotate::files(&history_path(), GENERATIONS)
+        .iter()
+        .filter(|p| p.exists())
+    {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        for line in BufReader::new(file).lines() {
+            // Skip lines that were cut short or written by an older format
+            if let Ok(record) = serde_json::from_str::<Record>(&line?) {
+                if record.ts >= since && record.context == context {
+                    records.push(record);
+                }
+            }
+        }
+    }
+    records.sort_by_key(|r| std::cmp::Reverse(r.ts));
+    Ok(records)
+}
@@ -3,6 +3,10 @@
 //! Handles deployment of Titan Protocol services to various
 //! environments (staging, production, edge).
 
+pub mod history;
+
+use crate::utils::config::Config;
+use crate::utils::registry::{self, SERVICES};
 use crate::utils::ui;
 use anyhow::Result;
 use colored::Colorize;
@@ -13,25 +17,18 @@ use tokio::time::sleep;
 
 /// Execute the deploy command
 pub async fn execute(service: &str, env: &str, skip_confirm: bool, verbose: bool) -> Result<()> {
+    // Resolve the service name against the registry ("all" deploys everything)
+    let service_lower = if service.eq_ignore_ascii_case("all") {
+        "all".to_string()
+    } else {
+        registry::resolve(service)?.name.to_lowercase()
+    };
+    let service = service_lower.as_str();
+
     println!();
     ui::print_header("TITAN DEPLOYMENT ENGINE");
     println!();
 
-    // Validate service name
-    let valid_services = ["cerberus", "kronos", "hermes", "vortex", "opticus", "all"];
-    let service_lower = service.to_lowercase();
-
-    if !valid_services.contains(&service_lower.as_str()) {
-        println!("  {} Unknown service: {}", "✖".red().bold(), service.red());
-        println!();
-        println!("  {}", "Available services:".dimmed());
-        for svc in valid_services {
-            println!("    {} {}", "•".cyan(), svc);
-        }
-        println!();
-        return Ok(());
-    }
-
     // Display deployment info
     let icon = get_service_icon(&service_lower);
     println!(
@@ -100,6 +97,7 @@ pub async fn execute(service: &str, env: &str, skip_confirm: bool, verbose: bool
     }
 
     pb.finish_and_clear();
+    record_history(&service_lower, env, verbose);
 
     // Success message
     println!();
@@ -131,6 +129,34 @@ pub async fn execute(service: &str, env: &str, skip_confirm: bool, verbose: bool
     Ok(())
 }
 
+/// Keep the deployment for `titan ask --with-deploys`
+fn record_history(service: &str, env: &str, verbose: bool) {
+    let context = Config::load().unwrap_or_default().context();
+    let names: Vec<String> = if service == "all" {
+        SERVICES.iter().map(|s| s.name.to_lowercase()).collect()
+    } else {
+        vec![service.to_string()]
+    };
+    let ts = chrono::Utc::now();
+    let records: Vec<history::Record> = names
+        .into_iter()
+        .map(|name| history::Record {
+            ts,
+            service: name.to_uppercase(),
+            env: env.to_string(),
+            image: format!("titan/{}:latest", name),
+            context: context.clone(),
+            user: std::env::var("USER").ok(),
+        })
+        .collect();
+
+    if let Err(e) = history::record(&records) {
+        if verbose {
+            ui::print_warning(&format!("Could not record deployment history: {}", e));
+        }
+    }
+}
+
 /// Get the icon for a service
 fn get_service_icon(service: &str) -> &'static str {
     match service.to_lowercase().as_str() {
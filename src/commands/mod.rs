@@ -3,10 +3,20 @@
 //! This module exports all available CLI commands:
 //! - status: Check service health
 //! - ask: Query VORTEX AI
+//! - cache: Inspect and clear the VORTEX response cache
 //! - deploy: Deploy services
+//! - models: List VORTEX models
+//! - sessions: Manage stored VORTEX conversations
+//! - templates: Manage prompt templates
+//! - usage: Summarize VORTEX token usage and spend
 //! - vision: Connect to OPTICUS
 
 pub mod ask;
+pub mod cache;
 pub mod deploy;
+pub mod models;
+pub mod sessions;
 pub mod status;
+pub mod templates;
+pub mod usage;
 pub mod vision;
@@ -0,0 +1,116 @@
+//! Models command implementation
+//!
+//! Lists the models VORTEX serves, with their limits and deprecation
+//! status, along with the aliases configured under `[vortex.aliases]`.
+
+use super::ask;
+use crate::utils::config::Config;
+use crate::utils::vortex::{ModelInfo, VortexClient};
+use crate::utils::{duration, models, ui};
+use anyhow::Result;
+use colored::Colorize;
+
+/// Print the model catalog, refetching it when `refresh` is set
+pub async fn execute(refresh: bool, verbose: bool) -> Result<()> {
+    let config = Config::load()?;
+    let vortex = VortexClient::new(&config.vortex)?;
+    let catalog = models::catalog(&vortex, &config.vortex, refresh).await?;
+
+    println!();
+    ui::print_header("VORTEX MODELS");
+    println!();
+
+    if catalog.stale {
+        ui::print_warning(&format!(
+            "Could not reach VORTEX; showing the list cached {} ago",
+            duration::humanize(catalog.age())
+        ));
+        println!();
+    }
+
+    if catalog.models.is_empty() {
+        println!("  {} VORTEX reports no models", "○".dimmed());
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "  {:<20} {:>8} {:>11}  {:<12} {}",
+        "MODEL".cyan().bold(),
+        "CONTEXT".cyan().bold(),
+        "MAX OUTPUT".cyan().bold(),
+        "INPUT".cyan().bold(),
+        "STATUS".cyan().bold()
+    );
+    println!("  {}", "─".repeat(70));
+
+    let default = ask::default_model(&config);
+    for model in &catalog.models {
+        let name = if model.id == default {
+            format!("{} *", model.id)
+        } else {
+            model.id.clone()
+        };
+        let input = if model.modalities.is_empty() {
+            "text".to_string()
+        } else {
+            model.modalities.join(", ")
+        };
+        println!(
+            "  {:<20} {:>8} {:>11}  {:<12} {}",
+            name,
+            format_tokens(model.context_length),
+            format_tokens(model.max_output_tokens),
+            input,
+            status(model)
+        );
+        if verbose {
+            if let Some(ref description) = model.description {
+                println!("    {}", description.dimmed());
+            }
+        }
+    }
+    println!("  {}", "─".repeat(70));
+    println!("  {}", "* default model".dimmed());
+
+    if !config.vortex.aliases.is_empty() {
+        println!();
+        println!("  {}", "Aliases:".cyan().bold());
+        for (alias, target) in &config.vortex.aliases {
+            let note = if catalog.find(target).is_none() {
+                format!(" {}", "(not served)".red())
+            } else {
+                String::new()
+            };
+            println!("    {:<10} → {}{}", alias, target, note);
+        }
+    }
+
+    println!();
+    println!(
+        "  {} {} · fetched {} ago",
+        "Source:".dimmed(),
+        catalog.url,
+        duration::humanize(catalog.age())
+    );
+    println!();
+    Ok(())
+}
+
+/// Active, or deprecated with its replacement
+fn status(model: &ModelInfo) -> String {
+    match (model.deprecated, &model.replacement) {
+        (false, _) => "active".green().to_string(),
+        (true, Some(replacement)) => format!("deprecated → {}", replacement).yellow().to_string(),
+        (true, None) => "deprecated".yellow().to_string(),
+    }
+}
+
+/// Token count shortened to thousands, e.g. `32K`
+fn format_tokens(tokens: Option<u64>) -> String {
+    match tokens {
+        None => "-".to_string(),
+        Some(n) if n >= 1024 && n % 1024 == 0 => format!("{}K", n / 1024),
+        Some(n) => n.to_string(),
+    }
+}
@@ -0,0 +1,171 @@
+//! Sessions command implementation
+//!
+//! Lists, shows, exports and deletes the VORTEX conversations stored
+//! by `titan ask --session`.
+
+use crate::utils::session::{self, Session};
+use crate::utils::ui;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use dialoguer::Confirm;
+
+/// Transcript formats for `titan sessions export`
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+}
+
+/// Print every stored session, most recent first
+pub fn list() -> Result<()> {
+    let sessions = session::list()?;
+
+    println!();
+    ui::print_header("VORTEX SESSIONS");
+    println!();
+
+    if sessions.is_empty() {
+        println!(
+            "  {} No sessions yet (start one with titan ask --session <id> \"...\")",
+            "○".dimmed()
+        );
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "  {:<24} {:>5}  {:<12} {:<10} {}",
+        "SESSION".cyan().bold(),
+        "MSGS".cyan().bold(),
+        "MODEL".cyan().bold(),
+        "UPDATED".cyan().bold(),
+        "FIRST QUESTION".cyan().bold()
+    );
+    println!("  {}", "─".repeat(76));
+
+    for session in &sessions {
+        let first = session
+            .turns
+            .iter()
+            .find(|t| t.role == "user")
+            .map(|t| preview(&t.content, 30))
+            .unwrap_or_default();
+        println!(
+            "  {:<24} {:>5}  {:<12} {:<10} {}",
+            session.id,
+            session.turns.len(),
+            session.model,
+            age(session),
+            first.dimmed()
+        );
+    }
+
+    println!("  {}", "─".repeat(76));
+    println!();
+    Ok(())
+}
+
+/// Print a session's transcript
+pub fn show(id: &str) -> Result<()> {
+    let session = session::load_existing(id)?;
+
+    println!();
+    ui::print_header(&format!("SESSION {}", session.id.to_uppercase()));
+    println!();
+    println!("  {} {}", "Model:".dimmed(), session.model);
+    println!(
+        "  {} {}",
+        "Started:".dimmed(),
+        session::format_time(&session.created)
+    );
+    if let Some(ref system) = session.system {
+        println!("  {} {}", "System:".dimmed(), system);
+    }
+
+    for turn in &session.turns {
+        println!();
+        let speaker = if turn.role == "user" {
+            "You".yellow().bold()
+        } else {
+            "🧠 VORTEX".cyan().bold()
+        };
+        println!(
+            "  {} {}",
+            speaker,
+            turn.timestamp.format("%H:%M:%S").to_string().dimmed()
+        );
+        for line in turn.content.lines() {
+            println!("    {}", line);
+        }
+    }
+
+    println!();
+    Ok(())
+}
+
+/// Write a session to stdout or a file as Markdown or JSON
+pub fn export(id: &str, format: ExportFormat, output: Option<&str>) -> Result<()> {
+    let session = session::load_existing(id)?;
+    let contents = match format {
+        ExportFormat::Markdown => session.to_markdown(),
+        ExportFormat::Json => serde_json::to_string_pretty(&session)? + "\n",
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, contents).with_context(|| format!("Failed to write {}", path))?;
+            ui::print_success(&format!("Exported {} to {}", session.id, path));
+        }
+        None => print!("{}", contents),
+    }
+    Ok(())
+}
+
+/// Delete a session after confirmation
+pub fn delete(id: &str, skip_confirm: bool) -> Result<()> {
+    let session = session::load_existing(id)?;
+
+    if !skip_confirm {
+        let proceed = Confirm::new()
+            .with_prompt(format!(
+                "  Delete session {} ({} messages)?",
+                session.id,
+                session.turns.len()
+            ))
+            .default(false)
+            .interact()?;
+        if !proceed {
+            println!("  {} Kept {}", "⚠".yellow().bold(), session.id);
+            return Ok(());
+        }
+    }
+
+    session::delete(id)?;
+    ui::print_success(&format!("Deleted session {}", id));
+    Ok(())
+}
+
+/// Shorten text to one line of at most `max` characters
+fn preview(text: &str, max: usize) -> String {
+    let line = text.lines().next().unwrap_or_default();
+    if line.chars().count() > max || text.lines().nth(1).is_some() {
+        let cut: String = line.chars().take(max.saturating_sub(1)).collect();
+        format!("{}…", cut)
+    } else {
+        line.to_string()
+    }
+}
+
+/// How long ago a session was last updated, e.g. "5m ago"
+fn age(session: &Session) -> String {
+    let elapsed = (Utc::now() - session.updated).to_std().unwrap_or_default();
+    let secs = elapsed.as_secs();
+    let coarse = match secs {
+        0..=59 => return "just now".to_string(),
+        60..=3599 => std::time::Duration::from_secs(secs / 60 * 60),
+        3600..=86_399 => std::time::Duration::from_secs(secs / 3600 * 3600),
+        _ => std::time::Duration::from_secs(secs / 86_400 * 86_400),
+    };
+    format!("{} ago", crate::utils::duration::humanize(coarse))
+}
@@ -0,0 +1,485 @@
+//! Connection phase diagnostics
+//!
+//! Replays a health check by hand so each phase can be timed on its own:
+//! DNS resolution, TCP connect, TLS handshake and time to first byte.
+//! Resolved addresses are tried in order until one accepts the connection.
+//! For HTTPS endpoints the negotiated protocol and the server certificate
+//! are reported as well.
+
+use crate::utils::registry::Service;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use reqwest::Url;
+use rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+use rustls::{Certificate, ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{lookup_host, TcpStream};
+use tokio::time::timeout;
+use tokio_rustls::TlsConnector;
+
+/// Per-phase time limit, matching the connect timeout of `TitanClient`
+const PHASE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Names of the phases in the order they run
+const PHASES: [&str; 4] = ["DNS resolve", "TCP connect", "TLS handshake", "First byte"];
+
+/// Result of a single connection phase
+enum Outcome {
+    Ok { elapsed: Duration, detail: String },
+    Failed(String),
+    Skipped(&'static str),
+}
+
+/// How close a certificate is to its expiry date
+#[derive(Debug, PartialEq)]
+enum Expiry {
+    Expired,
+    /// Within `cert_warn_days`
+    Soon,
+    Valid,
+}
+
+impl Expiry {
+    fn of(days_left: i64, warn_days: i64) -> Self {
+        if days_left < 0 {
+            Self::Expired
+        } else if days_left < warn_days {
+            Self::Soon
+        } else {
+            Self::Valid
+        }
+    }
+}
+
+/// Details of the certificate presented by the server
+struct CertInfo {
+    subject: String,
+    san: Vec<String>,
+    expires: DateTime<Utc>,
+    days_left: i64,
+    verification: Result<(), String>,
+}
+
+/// Full diagnosis of one endpoint
+#[derive(Default)]
+pub(super) struct Diagnosis {
+    phases: Vec<Outcome>,
+    protocol: Option<String>,
+    certificate: Option<CertInfo>,
+}
+
+impl Diagnosis {
+    fn ok(&mut self, elapsed: Duration, detail: impl Into<String>) {
+        self.phases.push(Outcome::Ok {
+            elapsed,
+            detail: detail.into(),
+        });
+    }
+
+    fn fail(&mut self, error: impl Into<String>) {
+        self.phases.push(Outcome::Failed(error.into()));
+    }
+}
+
+/// Run every connection phase against an endpoint
+pub(super) async fn diagnose(endpoint: &str) -> Diagnosis {
+    let mut diagnosis = Diagnosis::default();
+    run_phases(endpoint, &mut diagnosis).await;
+    diagnosis
+}
+
+async fn run_phases(endpoint: &str, d: &mut Diagnosis) {
+    let url = match Url::parse(endpoint) {
+        Ok(url) => url,
+        Err(e) => return d.fail(format!("invalid URL: {}", e)),
+    };
+    let host = url.host_str().unwrap_or_default().to_string();
+    let port = url.port_or_known_default().unwrap_or(80);
+    let path = match url.query() {
+        Some(q) => format!("{}?{}", url.path(), q),
+        None => url.path().to_string(),
+    };
+
+    // DNS resolve
+    let addrs: Vec<SocketAddr> = match timed(lookup_host((host.as_str(), port))).await {
+        Ok((addrs, elapsed)) => {
+            let addrs: Vec<SocketAddr> = addrs.collect();
+            if addrs.is_empty() {
+                return d.fail("no addresses returned");
+            }
+            let ips: Vec<String> = addrs.iter().map(|a| a.ip().to_string()).collect();
+            d.ok(elapsed, ips.join(", "));
+            addrs
+        }
+        Err(e) => return d.fail(e),
+    };
+
+    // TCP connect, falling back to the next address when one fails
+    let mut tcp = match connect(&addrs).await {
+        Ok((tcp, elapsed, detail)) => {
+            d.ok(elapsed, detail);
+            tcp
+        }
+        Err(e) => return d.fail(e),
+    };
+
+    if url.scheme() != "https" {
+        d.phases.push(Outcome::Skipped("plain HTTP"));
+        return first_byte_phase(&mut tcp, &host, &path, d, None).await;
+    }
+
+    // TLS handshake
+    let verifier = Arc::new(RecordingVerifier::new());
+    let mut config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+    let server_name = match ServerName::try_from(host.as_str()) {
+        Ok(name) => name,
+        Err(e) => return d.fail(e.to_string()),
+    };
+    let connector = TlsConnector::from(Arc::new(config));
+    let (mut tls, version) = match timed(connector.connect(server_name, tcp)).await {
+        Ok((tls, elapsed)) => {
+            let conn = tls.get_ref().1;
+            let version = match conn.protocol_version() {
+                Some(rustls::ProtocolVersion::TLSv1_3) => "TLSv1.3".to_string(),
+                Some(rustls::ProtocolVersion::TLSv1_2) => "TLSv1.2".to_string(),
+                Some(other) => format!("{:?}", other),
+                None => "unknown".to_string(),
+            };
+            d.ok(elapsed, version.clone());
+
+            d.certificate = conn
+                .peer_certificates()
+                .and_then(|certs| certs.first())
+                .and_then(|cert| parse_certificate(cert, verifier.outcome()));
+            (tls, version)
+        }
+        Err(e) => return d.fail(e),
+    };
+
+    first_byte_phase(&mut tls, &host, &path, d, Some(version)).await;
+}
+
+/// Connect to the first address that accepts, returning the time that
+/// attempt took and which address it was
+async fn connect(addrs: &[SocketAddr]) -> Result<(TcpStream, Duration, String), String> {
+    let mut failures = Vec::new();
+    for addr in addrs {
+        match timed(TcpStream::connect(addr)).await {
+            Ok((tcp, elapsed)) => {
+                let detail = match failures.len() {
+                    0 => addr.to_string(),
+                    n => format!("{} (after {} failed)", addr, n),
+                };
+                return Ok((tcp, elapsed, detail));
+            }
+            Err(e) => failures.push(format!("{}: {}", addr, e)),
+        }
+    }
+    Err(failures.join("; "))
+}
+
+/// Send a minimal GET request and time the first byte of the response
+async fn first_byte_phase<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    host: &str,
+    path: &str,
+    d: &mut Diagnosis,
+    tls_version: Option<String>,
+) {
+    match timeout(PHASE_TIMEOUT, first_byte(stream, host, path)).await {
+        Ok(Ok((elapsed, status_line))) => {
+            let http_version = status_line.split_whitespace().next().unwrap_or("HTTP");
+            d.protocol = Some(match tls_version {
+                Some(tls) => format!("{} / {}", tls, http_version),
+                None => http_version.to_string(),
+            });
+            d.ok(elapsed, status_line);
+        }
+        Ok(Err(e)) => d.fail(e.to_string()),
+        Err(_) => d.fail(format!("timed out after {}s", PHASE_TIMEOUT.as_secs())),
+    }
+}
+
+async fn first_byte<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    host: &str,
+    path: &str,
+) -> std::io::Result<(Duration, String)> {
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: TITAN-CLI/1.0\r\nConnection: close\r\n\r\n",
+        path, host
+    );
+    stream.write_all(request.as_bytes()).await?;
+    stream.flush().await?;
+
+    let start = Instant::now();
+    let mut buf = vec![0u8; 1024];
+    let n = stream.read(&mut buf).await?;
+    let elapsed = start.elapsed();
+    if n == 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "connection closed before response",
+        ));
+    }
+
+    let head = String::from_utf8_lossy(&buf[..n]);
+    let status_line = head.lines().next().unwrap_or_default().trim().to_string();
+    Ok((elapsed, status_line))
+}
+
+/// Await a fallible future under the phase timeout, returning its duration
+async fn timed<T, E, F>(fut: F) -> Result<(T, Duration), String>
+where
+    F: Future<Output = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    let start = Instant::now();
+    match timeout(PHASE_TIMEOUT, fut).await {
+        Ok(Ok(value)) => Ok((value, start.elapsed())),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(format!("timed out after {}s", PHASE_TIMEOUT.as_secs())),
+    }
+}
+
+/// Extract subject, SAN and expiry from a DER certificate
+fn parse_certificate(cert: &Certificate, verification: Result<(), String>) -> Option<CertInfo> {
+    let (_, parsed) = x509_parser::parse_x509_certificate(&cert.0).ok()?;
+
+    let san = parsed
+        .subject_alternative_name()
+        .ok()
+        .flatten()
+        .map(|ext| {
+            ext.value
+                .general_names
+                .iter()
+                .map(|name| match name {
+                    x509_parser::extensions::GeneralName::DNSName(dns) => dns.to_string(),
+                    other => other.to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let not_after = parsed.validity().not_after.timestamp();
+    let expires = DateTime::<Utc>::from_timestamp(not_after, 0)?;
+    let days_left = (expires - Utc::now()).num_days();
+
+    Some(CertInfo {
+        subject: parsed.subject().to_string(),
+        san,
+        expires,
+        days_left,
+        verification,
+    })
+}
+
+/// Certificate verifier that records the WebPKI verdict but never aborts
+///
+/// Diagnostics should still show the certificate of a server whose chain is
+/// invalid or expired, so the failure is reported instead of enforced.
+struct RecordingVerifier {
+    inner: WebPkiVerifier,
+    outcome: Mutex<Result<(), String>>,
+}
+
+impl RecordingVerifier {
+    fn new() -> Self {
+        let mut roots = RootCertStore::empty();
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(
+                ta.subject,
+                ta.spki,
+                ta.name_constraints,
+            )
+        }));
+
+        Self {
+            inner: WebPkiVerifier::new(roots, None),
+            outcome: Mutex::new(Ok(())),
+        }
+    }
+
+    fn outcome(&self) -> Result<(), String> {
+        self.outcome.lock().map(|o| o.clone()).unwrap_or(Ok(()))
+    }
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verdict = self
+            .inner
+            .verify_server_cert(
+                end_entity,
+                intermediates,
+                server_name,
+                scts,
+                ocsp_response,
+                now,
+            )
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+
+        if let Ok(mut outcome) = self.outcome.lock() {
+            *outcome = verdict;
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// Print the phase breakdown and certificate details for one service
+pub(super) fn print_diagnosis(service: &Service, diagnosis: &Diagnosis, cert_warn_days: i64) {
+    println!(
+        "  {} {}  {}",
+        service.icon,
+        service.name.cyan().bold(),
+        service.endpoint.dimmed()
+    );
+
+    for (i, name) in PHASES.iter().enumerate() {
+        let label = format!("{:<15}", name);
+        match diagnosis.phases.get(i) {
+            Some(Outcome::Ok { elapsed, detail }) => println!(
+                "    {} {} {:>9}  {}",
+                "✓".green(),
+                label.dimmed(),
+                format_duration(*elapsed),
+                detail
+            ),
+            Some(Outcome::Failed(error)) => println!(
+                "    {} {} {:>9}  {}",
+                "✖".red().bold(),
+                label.dimmed(),
+                "failed".red(),
+                error.red()
+            ),
+            Some(Outcome::Skipped(reason)) => println!(
+                "    {} {} {:>9}  {}",
+                "–".dimmed(),
+                label.dimmed(),
+                "–",
+                reason.dimmed()
+            ),
+            None => println!(
+                "    {} {} {:>9}",
+                "–".dimmed(),
+                label.dimmed(),
+                "skipped".dimmed()
+            ),
+        }
+    }
+
+    if let Some(ref protocol) = diagnosis.protocol {
+        println!("      {} {}", "Protocol:".dimmed(), protocol);
+    }
+
+    if let Some(ref cert) = diagnosis.certificate {
+        println!("      {} {}", "Subject:".dimmed(), cert.subject);
+        if !cert.san.is_empty() {
+            println!("      {} {}", "SAN:".dimmed(), cert.san.join(", "));
+        }
+
+        let expiry = format!(
+            "{} ({} days)",
+            cert.expires.format("%Y-%m-%d"),
+            cert.days_left
+        );
+        match Expiry::of(cert.days_left, cert_warn_days) {
+            Expiry::Expired => {
+                println!("      {} {}", "Expires:".dimmed(), expiry.red().bold());
+                println!("      {} certificate has expired", "✖".red().bold());
+            }
+            Expiry::Soon => {
+                println!("      {} {}", "Expires:".dimmed(), expiry.yellow());
+                println!(
+                    "      {} certificate expires within {} days",
+                    "⚠".yellow().bold(),
+                    cert_warn_days
+                );
+            }
+            Expiry::Valid => println!("      {} {}", "Expires:".dimmed(), expiry),
+        }
+
+        if let Err(ref e) = cert.verification {
+            println!(
+                "      {} verification failed: {}",
+                "✖".red().bold(),
+                e.red()
+            );
+        }
+    }
+}
+
+/// Format a phase duration with sub-millisecond precision
+fn format_duration(d: Duration) -> String {
+    format!("{:.1}ms", d.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Self-signed certificate for cerberus.titan.local
+    const CERT_PEM: &str = "\
+-----BEGIN CERTIFICATE-----\n\
+MIIByzCCAXGgAwIBAgIUWuRGdDoN3HtRDv2GKKQaxys+7wEwCgYIKoZIzj0EAwIw\n\
+HzEdMBsGA1UEAwwUY2VyYmVydXMudGl0YW4ubG9jYWwwHhcNMjYxMDE4MjE1NjAy\n\
+WhcNMjYxMTE3MjE1NjAyWjAfMR0wGwYDVQQDDBRjZXJiZXJ1cy50aXRhbi5sb2Nh\n\
+bDBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABEgZPs8S5gKCOqCAHYN8QQNdXPE3\n\
+gnsPyVZs291iQR7CHO6IQvAeDKJm+pZ5Ft/qaTj3anOBgViOqnqU5H2IC3qjgYow\n\
+gYcwHQYDVR0OBBYEFGHURylcway0mC2uACXiheOL/qZiMB8GA1UdIwQYMBaAFGHU\n\
+Rylcway0mC2uACXiheOL/qZiMA8GA1UdEwEB/wQFMAMBAf8wNAYDVR0RBC0wK4IU\n\
+Y2VyYmVydXMudGl0YW4ubG9jYWyCE2dhdGV3YXkudGl0YW4ubG9jYWwwCgYIKoZI\n\
+zj0EAwIDSAAwRQIhAJU3ljmTo9CCD6xWcG1JZMT0+BxYQ/KwYH9ysHCztHu3AiAq\n\
+9g0uGCQSaVQA9OV0lK8KN2oZmRWNxblmv3c9p7BYTA==\n\
+-----END CERTIFICATE-----\n";
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::ZERO), "0.0ms");
+        assert_eq!(format_duration(Duration::from_micros(1250)), "1.2ms");
+        assert_eq!(format_duration(Duration::from_millis(3000)), "3000.0ms");
+    }
+
+    #[test]
+    fn test_expiry_thresholds() {
+        assert_eq!(Expiry::of(-1, 14), Expiry::Expired);
+        assert_eq!(Expiry::of(0, 14), Expiry::Soon);
+        assert_eq!(Expiry::of(13, 14), Expiry::Soon);
+        assert_eq!(Expiry::of(14, 14), Expiry::Valid);
+        assert_eq!(Expiry::of(5, 0), Expiry::Valid);
+    }
+
+    #[test]
+    fn test_parse_certificate() {
+        let (_, pem) = x509_parser::pem::parse_x509_pem(CERT_PEM.as_bytes()).unwrap();
+        let cert = Certificate(pem.contents);
+        let info = parse_certificate(&cert, Err("self-signed".to_string())).unwrap();
+
+        assert_eq!(info.subject, "CN=cerberus.titan.local");
+        assert_eq!(info.san, ["cerberus.titan.local", "gateway.titan.local"]);
+        assert_eq!(info.expires.to_rfc3339(), "2026-11-17T21:56:02+00:00");
+        assert!(info.days_left <= 30);
+        assert!(info.verification.is_err());
+
+        assert!(parse_certificate(&Certificate(vec![0x30, 0x03]), Ok(())).is_none());
+    }
+}
@@ -0,0 +1,363 @@
+//! Persistent status history
+//!
+//! Every `titan status` run appends one record per checked service to
+//! `~/.titan/status-history.jsonl`. The file is rotated once it grows past
+//! a size limit, and `titan status history` turns the records into uptime
+//! reports.
+
+use crate::utils::client::HealthProbe;
+use crate::utils::registry::Service;
+use crate::utils::{config, duration, rotate, ui};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Rotate the history file once it grows past this size
+const MAX_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Number of rotated files kept next to the active one
+const GENERATIONS: usize = 3;
+
+/// A single health check result as stored on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Record {
+    pub ts: DateTime<Utc>,
+    pub service: String,
+    pub context: String,
+    pub up: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A contiguous run of failed checks
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Incident {
+    pub start: DateTime<Utc>,
+    /// First successful check after the outage, `None` while still down
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl Incident {
+    fn duration(&self, now: DateTime<Utc>) -> Duration {
+        (self.end.unwrap_or(now) - self.start)
+            .to_std()
+            .unwrap_or_default()
+    }
+}
+
+/// Uptime statistics for one service
+pub(super) struct Summary {
+    pub checks: usize,
+    pub uptime_pct: f64,
+    pub mean_latency_ms: Option<f64>,
+    pub incidents: Vec<Incident>,
+    pub longest_outage: Option<Duration>,
+}
+
+/// Path of the active history file
+fn history_path() -> PathBuf {
+    config::titan_dir().join("status-history.jsonl")
+}
+
+/// Append the results of a status run to the history file
+pub(super) fn record(
+    results: &[(&Service, Result<HealthProbe, reqwest::Error>)],
+    context: &str,
+) -> Result<()> {
+    let path = history_path();
+    fs::create_dir_all(config::titan_dir())?;
+    rotate::rotate_if_needed(&path, MAX_BYTES, GENERATIONS)?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    let ts = Utc::now();
+    for (service, result) in results {
+        let record = Record::of(service, result, context, ts);
+        writeln!(file, "{}", serde_json::to_string(&record)?)?;
+    }
+
+    Ok(())
+}
+
+impl Record {
+    /// The stored form of one health check result
+    fn of(
+        service: &Service,
+        result: &Result<HealthProbe, reqwest::Error>,
+        context: &str,
+        ts: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            ts,
+            service: service.name.to_string(),
+            context: context.to_string(),
+            up: super::is_up(result),
+            http_status: result.as_ref().ok().map(|p| p.status.as_u16()),
+            latency_ms: result
+                .as_ref()
+                .ok()
+                .map(|p| p.latency.as_secs_f64() * 1000.0),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        }
+    }
+}
+
+/// Load all records newer than `since`, oldest first
+fn load(since: DateTime<Utc>) -> Result<Vec<Record>> {
+    let mut records = Vec::new();
+    for path in rotate::files(&history_path(), GENERATIONS)
+        .iter()
+        .filter(|p| p.exists())
+    {
+        let file = fs::File::open(path)?;
+        for line in BufReader::new(file).lines() {
+            // Skip lines that were cut short or written by an older format
+            if let Ok(record) = serde_json::from_str::<Record>(&line?) {
+                if record.ts >= since {
+                    records.push(record);
+                }
+            }
+        }
+    }
+
+    records.sort_by_key(|r| r.ts);
+    Ok(records)
+}
+
+/// Compute uptime, latency and outage windows from one service's records
+pub(super) fn summarize(records: &[&Record], now: DateTime<Utc>) -> Summary {
+    let checks = records.len();
+    let up = records.iter().filter(|r| r.up).count();
+
+    let latencies: Vec<f64> = records.iter().filter_map(|r| r.latency_ms).collect();
+    let mean_latency_ms = if latencies.is_empty() {
+        None
+    } else {
+        Some(latencies.iter().sum::<f64>() / latencies.len() as f64)
+    };
+
+    let mut incidents: Vec<Incident> = Vec::new();
+    for record in records {
+        let open = incidents.last_mut().filter(|i| i.end.is_none());
+        match (open, record.up) {
+            (Some(open), true) => open.end = Some(record.ts),
+            (None, false) => incidents.push(Incident {
+                start: record.ts,
+                end: None,
+            }),
+            _ => {}
+        }
+    }
+
+    let longest_outage = incidents.iter().map(|i| i.duration(now)).max();
+
+    Summary {
+        checks,
+        uptime_pct: if checks == 0 {
+            0.0
+        } else {
+            up as f64 * 100.0 / checks as f64
+        },
+        mean_latency_ms,
+        incidents,
+        longest_outage,
+    }
+}
+
+/// Uptime statistics of every service with records in the window
+pub(super) fn summaries(
+    services: &[&'static Service],
+    since: Duration,
+    context: &str,
+) -> Result<Vec<(&'static Service, Summary)>> {
+    let now = Utc::now();
+    let records: Vec<Record> = load(now - chrono::Duration::from_std(since)?)?
+        .into_iter()
+        .filter(|r| r.context == context)
+        .collect();
+
+    Ok(services
+        .iter()
+        .filter_map(|service| {
+            let mine: Vec<&Record> = records
+                .iter()
+                .filter(|r| r.service == service.name)
+                .collect();
+            (!mine.is_empty()).then(|| (*service, summarize(&mine, now)))
+        })
+        .collect())
+}
+
+/// Print the uptime report for `titan status history`
+pub(super) fn report(services: &[&'static Service], since: Duration, context: &str) -> Result<()> {
+    println!();
+    ui::print_header("TITAN STATUS HISTORY");
+    println!();
+
+    let now = Utc::now();
+    let summaries = summaries(services, since, context)?;
+
+    println!(
+        "  {} last {} {} context {}",
+        "Window:".dimmed(),
+        duration::humanize(since).white().bold(),
+        "·".dimmed(),
+        context.cyan()
+    );
+    println!();
+
+    if summaries.is_empty() {
+        println!(
+            "  {} No status history recorded in this window. Run {} to collect some.",
+            "ℹ".cyan().bold(),
+            "titan status".cyan()
+        );
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "  {:<16} {:>8} {:>8} {:>14} {:>16}",
+        "SERVICE".cyan().bold(),
+        "UPTIME".cyan().bold(),
+        "CHECKS".cyan().bold(),
+        "MEAN LATENCY".cyan().bold(),
+        "LONGEST OUTAGE".cyan().bold()
+    );
+    println!("  {}", "─".repeat(66));
+
+    let mut incident_lines = Vec::new();
+    for (service, summary) in &summaries {
+        let uptime = format!("{:.2}%", summary.uptime_pct);
+        let uptime = if summary.uptime_pct >= 99.9 {
+            uptime.green()
+        } else if summary.uptime_pct >= 95.0 {
+            uptime.yellow()
+        } else {
+            uptime.red()
+        };
+        let latency = summary
+            .mean_latency_ms
+            .map(|ms| format!("{:.1}ms", ms))
+            .unwrap_or_else(|| "–".to_string());
+        let longest = summary
+            .longest_outage
+            .map(duration::humanize)
+            .unwrap_or_else(|| "–".to_string());
+
+        let name_display = format!("{} {}", service.icon, service.name);
+        let padding = 16usize.saturating_sub(name_display.chars().count());
+        println!(
+            "  {}{} {:>8} {:>8} {:>14} {:>16}",
+            name_display,
+            " ".repeat(padding),
+            uptime,
+            summary.checks,
+            latency,
+            longest
+        );
+
+        for incident in &summary.incidents {
+            let end = match incident.end {
+                Some(end) => end.format("%Y-%m-%d %H:%M:%S").to_string(),
+                None => "ongoing".red().to_string(),
+            };
+            incident_lines.push(format!(
+                "    {} {:<9} {} → {} ({})",
+                "✖".red(),
+                service.name,
+                incident.start.format("%Y-%m-%d %H:%M:%S"),
+                end,
+                duration::humanize(incident.duration(now))
+            ));
+        }
+    }
+
+    println!("  {}", "─".repeat(66));
+
+    println!();
+    if incident_lines.is_empty() {
+        println!("  {} No incidents in this window", "✓".green().bold());
+    } else {
+        println!("  {}", "Incident Windows (UTC):".cyan().bold());
+        for line in incident_lines {
+            println!("{}", line);
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rec(minute: i64, up: bool) -> Record {
+        Record {
+            ts: DateTime::<Utc>::from_timestamp(minute * 60, 0).unwrap(),
+            service: "VORTEX".to_string(),
+            context: "local".to_string(),
+            up,
+            http_status: up.then_some(200),
+            latency_ms: up.then_some(10.0),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_summary_tracks_incident_windows() {
+        let records = [
+            rec(0, true),
+            rec(1, false),
+            rec(2, false),
+            rec(3, true),
+            rec(4, false),
+        ];
+        let refs: Vec<&Record> = records.iter().collect();
+        let now = DateTime::<Utc>::from_timestamp(10 * 60, 0).unwrap();
+        let summary = summarize(&refs, now);
+
+        assert_eq!(summary.checks, 5);
+        assert!((summary.uptime_pct - 40.0).abs() < f64::EPSILON);
+        assert_eq!(summary.mean_latency_ms, Some(10.0));
+        assert_eq!(summary.incidents.len(), 2);
+        assert_eq!(summary.incidents[0].end, Some(records[3].ts));
+        assert_eq!(summary.incidents[1].end, None);
+        // The ongoing outage (minute 4 until now) is the longest
+        assert_eq!(summary.longest_outage, Some(Duration::from_secs(6 * 60)));
+    }
+
+    #[test]
+    fn test_error_status_is_recorded_as_down() {
+        let service = &crate::utils::registry::SERVICES[0];
+        let probe = |code| {
+            Ok(HealthProbe {
+                status: reqwest::StatusCode::from_u16(code).unwrap(),
+                latency: Duration::from_millis(12),
+            })
+        };
+        let ts = Utc::now();
+
+        let healthy = Record::of(service, &probe(200), "local", ts);
+        assert!(healthy.up);
+
+        let failing = Record::of(service, &probe(503), "local", ts);
+        assert!(!failing.up);
+        assert_eq!(failing.http_status, Some(503));
+        assert_eq!(failing.latency_ms, Some(12.0));
+    }
+}
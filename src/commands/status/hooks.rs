@@ -0,0 +1,408 @@
+//! State-transition hooks for watch mode
+//!
+//! Tracks the health state of every watched service, confirms a change
+//! once it has held for the debounce period and fires the configured
+//! command or webhook actions. Services that change state too often are
+//! marked as flapping and their actions are held back until they settle.
+
+use crate::utils::client::{HealthProbe, TitanClient};
+use crate::utils::config::{AlertConfig, HookConfig};
+use crate::utils::registry;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+/// Time limit for a single hook command
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Coarse health of a service as seen by one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum HealthState {
+    /// Responded with a success status
+    Healthy,
+    /// Responded, but with an error status
+    Degraded,
+    /// Did not respond at all
+    Down,
+}
+
+impl HealthState {
+    /// Classify a health probe result
+    pub(super) fn of(result: &Result<HealthProbe, reqwest::Error>) -> Self {
+        match result {
+            Ok(probe) if probe.status.is_success() => Self::Healthy,
+            Ok(_) => Self::Degraded,
+            Err(_) => Self::Down,
+        }
+    }
+
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            Self::Healthy => "healthy",
+            Self::Degraded => "degraded",
+            Self::Down => "down",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "healthy" | "up" => Some(Self::Healthy),
+            "degraded" => Some(Self::Degraded),
+            "down" => Some(Self::Down),
+            _ => None,
+        }
+    }
+}
+
+/// A confirmed state change, as passed to hook actions
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct Transition {
+    pub service: &'static str,
+    pub from: HealthState,
+    pub to: HealthState,
+    pub timestamp: DateTime<Utc>,
+    pub context: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// What the tracker concluded from one observation
+#[derive(Debug, PartialEq)]
+pub(super) enum Decision {
+    /// No confirmed change
+    Nothing,
+    /// A change from this state was confirmed and actions should fire
+    Fire(HealthState),
+    /// The service just started flapping; actions are held back
+    FlappingStarted,
+    /// A change was confirmed while flapping and suppressed
+    Suppressed,
+    /// Flapping ended without a net change of state
+    Settled,
+}
+
+/// Per-service bookkeeping
+struct ServiceState {
+    /// Last confirmed state
+    reported: HealthState,
+    /// A differing state seen recently and when it was first seen
+    pending: Option<(HealthState, Instant)>,
+    /// Times of recent confirmed changes, for flap detection
+    changes: VecDeque<Instant>,
+    /// Confirmed state at the moment flapping began
+    flap_origin: Option<HealthState>,
+}
+
+/// Debounces state changes and detects flapping services
+pub(super) struct Tracker {
+    debounce: Duration,
+    flap_threshold: usize,
+    flap_window: Duration,
+    services: HashMap<&'static str, ServiceState>,
+}
+
+impl Tracker {
+    pub(super) fn new(config: &AlertConfig) -> Self {
+        Self {
+            debounce: config.debounce,
+            flap_threshold: config.flap_threshold.max(2),
+            flap_window: config.flap_window,
+            services: HashMap::new(),
+        }
+    }
+
+    /// Feed one observed state and decide whether actions should fire
+    pub(super) fn observe(
+        &mut self,
+        service: &'static str,
+        state: HealthState,
+        now: Instant,
+    ) -> Decision {
+        // The first observation only establishes the baseline
+        let Some(entry) = self.services.get_mut(service) else {
+            self.services.insert(
+                service,
+                ServiceState {
+                    reported: state,
+                    pending: None,
+                    changes: VecDeque::new(),
+                    flap_origin: None,
+                },
+            );
+            return Decision::Nothing;
+        };
+
+        while entry
+            .changes
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > self.flap_window)
+        {
+            entry.changes.pop_front();
+        }
+
+        if state == entry.reported {
+            entry.pending = None;
+
+            // A flapping service that has calmed down reports its net change once
+            if let Some(origin) = entry.flap_origin {
+                if entry.changes.len() < self.flap_threshold {
+                    entry.flap_origin = None;
+                    return if origin == state {
+                        Decision::Settled
+                    } else {
+                        Decision::Fire(origin)
+                    };
+                }
+            }
+            return Decision::Nothing;
+        }
+
+        let since = match entry.pending {
+            Some((pending, since)) if pending == state => since,
+            _ => {
+                entry.pending = Some((state, now));
+                now
+            }
+        };
+        if now.duration_since(since) < self.debounce {
+            return Decision::Nothing;
+        }
+
+        // The new state held long enough to be confirmed
+        let from = entry.reported;
+        entry.reported = state;
+        entry.pending = None;
+        entry.changes.push_back(now);
+
+        if entry.changes.len() >= self.flap_threshold {
+            if entry.flap_origin.is_none() {
+                entry.flap_origin = Some(from);
+                return Decision::FlappingStarted;
+            }
+            return Decision::Suppressed;
+        }
+
+        match entry.flap_origin.take() {
+            Some(origin) if origin == state => Decision::Settled,
+            Some(origin) => Decision::Fire(origin),
+            None => Decision::Fire(from),
+        }
+    }
+}
+
+/// A validated hook, with its service resolved to the registry name
+pub(super) struct Hook<'a> {
+    config: &'a HookConfig,
+    service: Option<&'static str>,
+}
+
+impl Hook<'_> {
+    /// Whether the hook applies to a transition
+    fn applies(&self, transition: &Transition) -> bool {
+        self.service.map_or(true, |s| s == transition.service)
+            && self
+                .config
+                .on
+                .iter()
+                .any(|p| matches(p, transition.from, transition.to))
+    }
+}
+
+/// Validate the configured hooks, rejecting unknown services or states and
+/// hooks without any action
+pub(super) fn load(hooks: &[HookConfig]) -> Result<Vec<Hook<'_>>> {
+    let mut loaded = Vec::with_capacity(hooks.len());
+    for hook in hooks {
+        if hook.command.is_none() && hook.webhook.is_none() {
+            bail!(
+                "Alert hook for {:?} needs a `command` or a `webhook`",
+                hook.on
+            );
+        }
+        let service = match hook.service {
+            Some(ref service) => Some(registry::resolve(service)?.name),
+            None => None,
+        };
+        for pattern in &hook.on {
+            let (from, to) = split_pattern(pattern);
+            for side in [from, to] {
+                if side != "*" && HealthState::parse(side).is_none() {
+                    bail!(
+                        "Unknown state '{}' in alert pattern '{}' (use healthy, degraded, down or *)",
+                        side,
+                        pattern
+                    );
+                }
+            }
+        }
+        loaded.push(Hook {
+            config: hook,
+            service,
+        });
+    }
+    Ok(loaded)
+}
+
+/// Split `from->to` into its sides; a bare state means any transition into it
+fn split_pattern(pattern: &str) -> (&str, &str) {
+    match pattern.split_once("->") {
+        Some((from, to)) => (from.trim(), to.trim()),
+        None => ("*", pattern.trim()),
+    }
+}
+
+/// Whether a hook pattern matches a transition
+fn matches(pattern: &str, from: HealthState, to: HealthState) -> bool {
+    let side = |p: &str, state: HealthState| p == "*" || HealthState::parse(p) == Some(state);
+    let (f, t) = split_pattern(pattern);
+    side(f, from) && side(t, to)
+}
+
+/// Run every hook matching the transition, returning a log line per action
+pub(super) async fn fire(
+    hooks: &[Hook<'_>],
+    transition: &Transition,
+    client: &TitanClient,
+) -> Vec<String> {
+    let mut log = Vec::new();
+
+    for hook in hooks.iter().filter(|hook| hook.applies(transition)) {
+        let hook = hook.config;
+        if let Some(ref command) = hook.command {
+            log.push(match run_command(command, transition).await {
+                Ok(()) => format!("ran `{}`", command),
+                Err(e) => format!("command `{}` failed: {}", command, e),
+            });
+        }
+        if let Some(ref url) = hook.webhook {
+            log.push(match client.post(url, transition).await {
+                Ok(_) => format!("posted to {}", url),
+                Err(e) => format!("webhook {} failed: {}", url, e),
+            });
+        }
+    }
+
+    log
+}
+
+/// Run a hook command through the shell with the event in its environment
+async fn run_command(command: &str, transition: &Transition) -> Result<()> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    };
+
+    cmd.env("TITAN_SERVICE", transition.service)
+        .env("TITAN_FROM", transition.from.as_str())
+        .env("TITAN_TO", transition.to.as_str())
+        .env("TITAN_TIMESTAMP", transition.timestamp.to_rfc3339())
+        .env("TITAN_CONTEXT", &transition.context)
+        .env(
+            "TITAN_HTTP_STATUS",
+            transition
+                .http_status
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+        )
+        .env("TITAN_ERROR", transition.error.clone().unwrap_or_default())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    let status = tokio::time::timeout(COMMAND_TIMEOUT, cmd.status())
+        .await
+        .map_err(|_| anyhow::anyhow!("timed out after {}s", COMMAND_TIMEOUT.as_secs()))??;
+
+    if !status.success() {
+        bail!("exited with {}", status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use HealthState::*;
+
+    fn tracker(debounce_secs: u64) -> Tracker {
+        Tracker::new(&AlertConfig {
+            debounce: Duration::from_secs(debounce_secs),
+            flap_threshold: 3,
+            flap_window: Duration::from_secs(600),
+            hooks: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_debounce_waits_for_state_to_hold() {
+        let mut t = tracker(30);
+        let start = Instant::now();
+        let at = |s: u64| start + Duration::from_secs(s);
+
+        assert_eq!(t.observe("VORTEX", Healthy, at(0)), Decision::Nothing);
+        assert_eq!(t.observe("VORTEX", Down, at(10)), Decision::Nothing);
+        // Recovering before the debounce period discards the blip
+        assert_eq!(t.observe("VORTEX", Healthy, at(20)), Decision::Nothing);
+        assert_eq!(t.observe("VORTEX", Down, at(30)), Decision::Nothing);
+        assert_eq!(t.observe("VORTEX", Down, at(60)), Decision::Fire(Healthy));
+    }
+
+    #[test]
+    fn test_flapping_suppresses_until_settled() {
+        let mut t = tracker(0);
+        let start = Instant::now();
+        let at = |s: u64| start + Duration::from_secs(s);
+
+        t.observe("HERMES", Healthy, at(0));
+        assert_eq!(t.observe("HERMES", Down, at(10)), Decision::Fire(Healthy));
+        assert_eq!(t.observe("HERMES", Healthy, at(20)), Decision::Fire(Down));
+        assert_eq!(t.observe("HERMES", Down, at(30)), Decision::FlappingStarted);
+        assert_eq!(t.observe("HERMES", Healthy, at(40)), Decision::Suppressed);
+        assert_eq!(t.observe("HERMES", Down, at(50)), Decision::Suppressed);
+        // Once the window has drained the net change is reported once
+        assert_eq!(t.observe("HERMES", Down, at(700)), Decision::Fire(Healthy));
+    }
+
+    #[test]
+    fn test_hook_patterns() {
+        assert!(matches("healthy->down", Healthy, Down));
+        assert!(!matches("healthy->down", Degraded, Down));
+        assert!(matches("*->healthy", Down, Healthy));
+        assert!(matches("degraded", Healthy, Degraded));
+    }
+
+    #[test]
+    fn test_hook_service_is_resolved() {
+        let config = vec![HookConfig {
+            on: vec!["down".to_string()],
+            service: Some("vort".to_string()),
+            command: Some("true".to_string()),
+            webhook: None,
+        }];
+        let hooks = load(&config).unwrap();
+        let transition = |service| Transition {
+            service,
+            from: Healthy,
+            to: Down,
+            timestamp: Utc::now(),
+            context: "default".to_string(),
+            http_status: None,
+            error: None,
+        };
+
+        assert!(hooks[0].applies(&transition("VORTEX")));
+        assert!(!hooks[0].applies(&transition("HERMES")));
+    }
+}
@@ -0,0 +1,231 @@
+//! Prometheus exporter mode
+//!
+//! Runs the service health checks on an interval and serves the latest
+//! results in the Prometheus text exposition format, turning the CLI into
+//! a lightweight blackbox exporter.
+
+use crate::utils::client::{HealthProbe, TitanClient};
+use crate::utils::registry::Service;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::RwLock;
+
+/// Serve metrics on `addr`, refreshing them every `interval`
+pub(super) async fn serve(
+    addr: &str,
+    services: Vec<&'static Service>,
+    interval: Duration,
+    context: String,
+) -> Result<()> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind metrics listener on {}", addr))?;
+
+    println!(
+        "  {} Serving metrics on {} every {}s",
+        "📡".cyan(),
+        format!("http://{}/metrics", listener.local_addr()?)
+            .cyan()
+            .bold(),
+        interval.as_secs()
+    );
+    println!("  {} Press Ctrl+C to stop", "ℹ".cyan());
+    println!();
+
+    let body = Arc::new(RwLock::new(String::new()));
+
+    // Refresh the exposition in the background
+    let client = TitanClient::new()?;
+    let refresher = {
+        let body = Arc::clone(&body);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let mut results = Vec::with_capacity(services.len());
+                for service in &services {
+                    results.push((*service, client.probe(service.endpoint).await));
+                }
+                *body.write().await = render(&results, &context);
+            }
+        })
+    };
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                let body = Arc::clone(&body);
+                tokio::spawn(async move {
+                    let _ = handle_connection(stream, body).await;
+                });
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    refresher.abort();
+    println!("  {} Metrics exporter stopped", "⏹".yellow());
+    Ok(())
+}
+
+/// Answer a single scrape request
+async fn handle_connection(mut stream: TcpStream, body: Arc<RwLock<String>>) -> Result<()> {
+    let mut buf = [0u8; 1024];
+    let n = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let mut parts = request.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+
+    let (status, content_type, payload) = match (method, path) {
+        ("GET", "/metrics") => (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            body.read().await.clone(),
+        ),
+        ("GET", "/") => (
+            "200 OK",
+            "text/html; charset=utf-8",
+            "<html><body><h1>TITAN-CLI exporter</h1><a href=\"/metrics\">Metrics</a></body></html>\n"
+                .to_string(),
+        ),
+        _ => (
+            "404 Not Found",
+            "text/plain; charset=utf-8",
+            "not found\n".to_string(),
+        ),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        payload.len(),
+        payload
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Render check results in the Prometheus text format
+fn render(results: &[(&Service, Result<HealthProbe, reqwest::Error>)], context: &str) -> String {
+    let mut out = String::new();
+    let labels = |service: &Service| {
+        format!(
+            "service=\"{}\",context=\"{}\"",
+            escape(&service.name.to_lowercase()),
+            escape(context)
+        )
+    };
+
+    let _ = writeln!(
+        out,
+        "# HELP titan_service_up Whether the last health check got a successful response."
+    );
+    let _ = writeln!(out, "# TYPE titan_service_up gauge");
+    for (service, result) in results {
+        let up = super::is_up(result);
+        let _ = writeln!(out, "titan_service_up{{{}}} {}", labels(service), up as u8);
+    }
+
+    let _ = writeln!(
+        out,
+        "# HELP titan_service_latency_seconds Time until the health check response arrived."
+    );
+    let _ = writeln!(out, "# TYPE titan_service_latency_seconds gauge");
+    for (service, result) in results {
+        if let Ok(probe) = result {
+            let _ = writeln!(
+                out,
+                "titan_service_latency_seconds{{{}}} {}",
+                labels(service),
+                probe.latency.as_secs_f64()
+            );
+        }
+    }
+
+    let _ = writeln!(
+        out,
+        "# HELP titan_service_http_status HTTP status of the last health check, 0 if none."
+    );
+    let _ = writeln!(out, "# TYPE titan_service_http_status gauge");
+    for (service, result) in results {
+        let code = result.as_ref().map(|p| p.status.as_u16()).unwrap_or(0);
+        let _ = writeln!(
+            out,
+            "titan_service_http_status{{{}}} {}",
+            labels(service),
+            code
+        );
+    }
+
+    out
+}
+
+/// Escape a label value as required by the exposition format
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::client::HealthProbe;
+    use crate::utils::registry::SERVICES;
+
+    #[test]
+    fn test_escape_label_values() {
+        assert_eq!(escape("prod"), "prod");
+        assert_eq!(escape(r#"say "hi""#), r#"say \"hi\""#);
+        assert_eq!(escape(r"C:\titan"), r"C:\\titan");
+        assert_eq!(escape("two\nlines"), r"two\nlines");
+    }
+
+    #[test]
+    fn test_render_exposition() {
+        let probe = |code| {
+            Ok(HealthProbe {
+                status: reqwest::StatusCode::from_u16(code).unwrap(),
+                latency: Duration::from_millis(250),
+            })
+        };
+        let results = [(&SERVICES[0], probe(200)), (&SERVICES[1], probe(503))];
+        let out = render(&results, "prod \"eu\"");
+        let lines: Vec<&str> = out.lines().collect();
+
+        for metric in [
+            "titan_service_up",
+            "titan_service_latency_seconds",
+            "titan_service_http_status",
+        ] {
+            let help = lines
+                .iter()
+                .position(|l| l.starts_with(&format!("# HELP {} ", metric)))
+                .unwrap();
+            assert_eq!(lines[help + 1], format!("# TYPE {} gauge", metric));
+        }
+
+        let labels = |service: &Service| {
+            format!(
+                "service=\"{}\",context=\"prod \\\"eu\\\"\"",
+                service.name.to_lowercase()
+            )
+        };
+        let up = format!("titan_service_up{{{}}} 1", labels(&SERVICES[0]));
+        let down = format!("titan_service_up{{{}}} 0", labels(&SERVICES[1]));
+        let status = format!("titan_service_http_status{{{}}} 503", labels(&SERVICES[1]));
+        assert!(lines.contains(&up.as_str()));
+        assert!(lines.contains(&down.as_str()));
+        assert!(lines.contains(&status.as_str()));
+    }
+}
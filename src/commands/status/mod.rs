@@ -0,0 +1,390 @@
+//! Status command implementation
+//!
+//! Checks the health of all Titan Protocol services and displays
+//! a formatted status table with connection indicators.
+
+mod diagnose;
+mod history;
+mod hooks;
+mod metrics;
+mod synthetic;
+mod tree;
+mod versions;
+mod watch;
+
+use crate::utils::client::{HealthProbe, TitanClient};
+use crate::utils::registry::{self, Service, SERVICES};
+use crate::utils::{config::Config, ui};
+use anyhow::Result;
+use colored::Colorize;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// Options accepted by the status command
+pub struct StatusOptions {
+    /// Check a specific service only
+    pub service: Option<String>,
+    /// Show detailed information
+    pub detailed: bool,
+    /// Render services as a dependency tree
+    pub tree: bool,
+    /// Break each check into connection phases
+    pub diagnose: bool,
+    /// Override the certificate expiry warning threshold
+    pub cert_warn_days: Option<i64>,
+    /// Serve Prometheus metrics on this address instead of printing once
+    pub serve_metrics: Option<String>,
+    /// Compare reported versions against the compatibility matrix
+    pub versions: bool,
+    /// Run the end-to-end checks from the configuration file
+    pub synthetic: bool,
+    /// Keep re-checking and fire transition hooks on changes
+    pub watch: bool,
+    /// Seconds between checks in long-running modes
+    pub interval: u64,
+}
+
+/// Execute the status command
+pub async fn execute(opts: StatusOptions, verbose: bool) -> Result<()> {
+    let config = Config::load()?;
+
+    // Select services, resolving the filter against the registry
+    let mut selected: Vec<&'static Service> = match opts.service {
+        Some(ref name) => vec![registry::resolve(name)?],
+        None => SERVICES.iter().collect(),
+    };
+
+    // Print header
+    println!();
+    ui::print_header("TITAN PROTOCOL STATUS");
+    println!();
+
+    // The tree view needs the health of everything below the selection
+    if opts.tree {
+        selected = tree::with_dependencies(&selected);
+    }
+
+    // Exporter mode keeps checking in the background instead of printing a table
+    if let Some(ref addr) = opts.serve_metrics {
+        let interval = Duration::from_secs(opts.interval.max(1));
+        return metrics::serve(addr, selected, interval, config.context()).await;
+    }
+
+    // Watch mode re-checks on an interval and fires transition hooks
+    if opts.watch {
+        return watch::run(&opts, &config, &selected, verbose).await;
+    }
+
+    let client = TitanClient::new()?;
+    let results = check_services(&client, &selected, true).await;
+    record_history(&config, &results, verbose);
+
+    print_results(&opts, &selected, &results, verbose);
+
+    // Compare the versions every service reports
+    if opts.versions {
+        let reports = versions::collect(&client, &selected).await;
+        let findings = versions::evaluate(&reports, &config.status.compat)?;
+        versions::print_report(&reports, &findings);
+    }
+
+    // Run the configured end-to-end request flows
+    if opts.synthetic {
+        let checks = synthetic::run_all(&client, &config.status.synthetic).await;
+        synthetic::print_report(&checks, verbose);
+    }
+
+    // Break every check down into its connection phases
+    if opts.diagnose {
+        let warn_days = opts.cert_warn_days.unwrap_or(config.status.cert_warn_days);
+
+        println!();
+        println!("  {}", "Connection Diagnostics:".cyan().bold());
+        println!("  {}", "─".repeat(60));
+        for service in &selected {
+            let diagnosis = diagnose::diagnose(service.endpoint).await;
+            diagnose::print_diagnosis(service, &diagnosis, warn_days);
+            println!();
+        }
+        println!("  {}", "─".repeat(60));
+    }
+
+    print_summary(&results);
+
+    Ok(())
+}
+
+/// Health of one service, as attached to `titan ask --with-status`
+#[derive(Serialize)]
+pub struct ServiceHealth {
+    pub service: &'static str,
+    pub description: &'static str,
+    pub endpoint: &'static str,
+    pub up: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub depends_on: &'static [&'static str],
+    /// Failed dependencies that explain why this service is down
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub down_because_of: Vec<&'static str>,
+}
+
+/// Check every service without printing anything
+pub async fn snapshot() -> Result<Vec<ServiceHealth>> {
+    let client = TitanClient::new()?;
+    let selected: Vec<&'static Service> = SERVICES.iter().collect();
+    let results = check_services(&client, &selected, false).await;
+
+    let down: HashSet<&str> = results
+        .iter()
+        .filter(|(_, s)| !is_up(s))
+        .map(|(service, _)| service.name)
+        .collect();
+    let mut causes = tree::root_causes(&selected, &down);
+
+    Ok(results
+        .into_iter()
+        .map(|(service, result)| {
+            let down_because_of = causes
+                .iter_mut()
+                .find(|c| c.service == service.name)
+                .map(|c| std::mem::take(&mut c.causes))
+                .unwrap_or_default();
+            ServiceHealth {
+                service: service.name,
+                description: service.description,
+                endpoint: service.endpoint,
+                up: is_up(&result),
+                http_status: result.as_ref().ok().map(|p| p.status.as_u16()),
+                latency_ms: result.as_ref().ok().map(|p| p.latency.as_millis() as u64),
+                error: result.as_ref().err().map(describe_error),
+                depends_on: service.depends_on,
+                down_because_of,
+            }
+        })
+        .collect())
+}
+
+/// Check every selected service, optionally showing a progress spinner
+async fn check_services(
+    client: &TitanClient,
+    selected: &[&'static Service],
+    progress: bool,
+) -> Vec<(&'static Service, Result<HealthProbe, reqwest::Error>)> {
+    // Create progress bar for scanning
+    let pb = if progress {
+        ProgressBar::new(selected.len() as u64)
+    } else {
+        ProgressBar::hidden()
+    };
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.cyan} {msg}")
+            .unwrap()
+            .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
+    );
+    pb.enable_steady_tick(Duration::from_millis(80));
+    pb.set_message("Scanning services...");
+
+    // Check each service
+    let mut results = Vec::new();
+
+    for service in selected {
+        pb.set_message(format!("Checking {}...", service.name));
+
+        let status = client.probe(service.endpoint).await;
+        results.push((*service, status));
+
+        pb.inc(1);
+    }
+
+    pb.finish_and_clear();
+    results
+}
+
+/// Keep the results for `titan status history`
+fn record_history(
+    config: &Config,
+    results: &[(&'static Service, Result<HealthProbe, reqwest::Error>)],
+    verbose: bool,
+) {
+    if !config.status.record_history {
+        return;
+    }
+    if let Err(e) = history::record(results, &config.context()) {
+        if verbose {
+            ui::print_warning(&format!("Could not record status history: {}", e));
+        }
+    }
+}
+
+/// Print results as a table or a dependency tree, followed by root causes
+fn print_results(
+    opts: &StatusOptions,
+    selected: &[&'static Service],
+    results: &[(&'static Service, Result<HealthProbe, reqwest::Error>)],
+    verbose: bool,
+) {
+    let down: HashSet<&str> = results
+        .iter()
+        .filter(|(_, s)| !is_up(s))
+        .map(|(service, _)| service.name)
+        .collect();
+
+    if opts.tree {
+        tree::print_tree(selected, &down);
+    } else {
+        print_status_table(results, opts.detailed, verbose);
+    }
+    tree::print_root_causes(selected, &down);
+}
+
+/// Print the online/total summary line
+fn print_summary(results: &[(&'static Service, Result<HealthProbe, reqwest::Error>)]) {
+    let online = results.iter().filter(|(_, s)| is_up(s)).count();
+    let total = results.len();
+
+    println!();
+    if online == total {
+        println!(
+            "  {} All {} services operational",
+            "✓".green().bold(),
+            total
+        );
+    } else {
+        println!(
+            "  {} {}/{} services online",
+            "⚠".yellow().bold(),
+            online,
+            total
+        );
+    }
+    println!();
+}
+
+/// Print the uptime report built from recorded status runs
+pub fn show_history(service: Option<String>, since: Duration) -> Result<()> {
+    let config = Config::load()?;
+    let selected: Vec<&'static Service> = match service {
+        Some(ref name) => vec![registry::resolve(name)?],
+        None => SERVICES.iter().collect(),
+    };
+    history::report(&selected, since, &config.context())
+}
+
+/// Uptime of one service, as offered to VORTEX by `titan ask --tools`
+#[derive(Serialize)]
+pub struct Uptime {
+    pub service: &'static str,
+    pub checks: usize,
+    pub uptime_pct: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mean_latency_ms: Option<f64>,
+    pub outages: Vec<history::Incident>,
+}
+
+/// Uptime and outages from recorded status runs, without printing
+pub fn uptime(service: Option<&str>, since: Duration) -> Result<Vec<Uptime>> {
+    let config = Config::load()?;
+    let selected: Vec<&'static Service> = match service {
+        Some(name) => vec![registry::resolve(name)?],
+        None => SERVICES.iter().collect(),
+    };
+    Ok(history::summaries(&selected, since, &config.context())?
+        .into_iter()
+        .map(|(service, summary)| Uptime {
+            service: service.name,
+            checks: summary.checks,
+            uptime_pct: summary.uptime_pct,
+            mean_latency_ms: summary.mean_latency_ms,
+            outages: summary.incidents,
+        })
+        .collect())
+}
+
+/// Print the status table
+fn print_status_table(
+    results: &[(&Service, Result<HealthProbe, reqwest::Error>)],
+    detailed: bool,
+    _verbose: bool,
+) {
+    // Table header
+    println!(
+        "  {} {} {} {}",
+        "SERVICE".cyan().bold(),
+        " ".repeat(10),
+        "STATUS".cyan().bold(),
+        if detailed {
+            "       DETAILS".cyan().bold().to_string()
+        } else {
+            "".to_string()
+        }
+    );
+    println!("  {}", "─".repeat(60));
+
+    for (service, status) in results {
+        let up = is_up(status);
+        let (status_text, status_color) = match status {
+            Ok(probe) if up => {
+                let display = format!("[ONLINE]  {}", "●".green());
+                (display, probe.status.to_string())
+            }
+            Ok(probe) => {
+                let display = format!("[OFFLINE] {}", "○".red());
+                (display, format!("HTTP {}", probe.status))
+            }
+            Err(e) => {
+                let display = format!("[OFFLINE] {}", "○".red());
+                (display, describe_error(e))
+            }
+        };
+
+        // Service name with icon
+        let name_display = format!("{} {}", service.icon, service.name);
+        let padding = 16 - name_display.chars().count();
+
+        print!("  {}{}", name_display, " ".repeat(padding.max(1)));
+
+        if up {
+            print!("{}", status_text.green());
+        } else {
+            print!("{}", status_text.red());
+        }
+
+        if detailed {
+            print!("  {}", service.description.dimmed());
+            if !up {
+                print!(" - {}", status_color.red().dimmed());
+            }
+        }
+
+        println!();
+    }
+
+    println!("  {}", "─".repeat(60));
+}
+
+/// Whether a health check got a successful response; an endpoint that
+/// answers with an error status counts as down
+pub(super) fn is_up(result: &Result<HealthProbe, reqwest::Error>) -> bool {
+    matches!(result, Ok(probe) if probe.status.is_success())
+}
+
+/// Summarize why a health check request failed
+fn describe_error(error: &reqwest::Error) -> String {
+    if error.is_timeout() {
+        "Timed out".to_string()
+    } else if error.is_connect() {
+        "Connection failed (run with --diagnose for details)".to_string()
+    } else if error.is_request() {
+        "Request failed".to_string()
+    } else {
+        error.to_string()
+    }
+}
@@ -0,0 +1,387 @@
+//! Synthetic end-to-end checks
+//!
+//! Runs the multi-step HTTP sequences configured under
+//! `[[status.synthetic]]`. Each step asserts on the response status,
+//! latency and JSON paths, and may capture values for later steps.
+
+use crate::utils::client::TitanClient;
+use crate::utils::config::{StepExpect, SyntheticCheck, SyntheticStep};
+use crate::utils::registry;
+use anyhow::{anyhow, bail, Result};
+use colored::Colorize;
+use reqwest::{Method, Url};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Outcome of a single step
+pub(super) struct StepResult {
+    pub name: String,
+    pub status: Option<u16>,
+    pub latency: Option<Duration>,
+    pub failures: Vec<String>,
+}
+
+/// Outcome of a whole check; steps after the first failure are not run
+pub(super) struct CheckResult {
+    pub name: String,
+    pub total_steps: usize,
+    pub steps: Vec<StepResult>,
+}
+
+impl CheckResult {
+    pub fn passed(&self) -> bool {
+        self.steps.len() == self.total_steps && self.steps.iter().all(|s| s.failures.is_empty())
+    }
+}
+
+/// Run every configured check in order
+pub(super) async fn run_all(client: &TitanClient, checks: &[SyntheticCheck]) -> Vec<CheckResult> {
+    let mut results = Vec::with_capacity(checks.len());
+    for check in checks {
+        results.push(run(client, check).await);
+    }
+    results
+}
+
+/// Run the steps of one check, stopping at the first failure
+async fn run(client: &TitanClient, check: &SyntheticCheck) -> CheckResult {
+    let mut vars: HashMap<String, String> = HashMap::new();
+    let mut steps = Vec::with_capacity(check.steps.len());
+
+    for (i, step) in check.steps.iter().enumerate() {
+        let name = step
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("step {}", i + 1));
+        let result = match run_step(client, step, &mut vars).await {
+            Ok((status, latency, failures)) => StepResult {
+                name,
+                status: Some(status),
+                latency: Some(latency),
+                failures,
+            },
+            Err(e) => StepResult {
+                name,
+                status: None,
+                latency: None,
+                failures: vec![e.to_string()],
+            },
+        };
+
+        let failed = !result.failures.is_empty();
+        steps.push(result);
+        if failed {
+            break;
+        }
+    }
+
+    CheckResult {
+        name: check.name.clone(),
+        total_steps: check.steps.len(),
+        steps,
+    }
+}
+
+/// Send one step's request and evaluate its assertions
+async fn run_step(
+    client: &TitanClient,
+    step: &SyntheticStep,
+    vars: &mut HashMap<String, String>,
+) -> Result<(u16, Duration, Vec<String>)> {
+    let url = substitute(&target_url(step)?, vars)?;
+    let method = Method::from_bytes(step.method.to_uppercase().as_bytes())
+        .map_err(|_| anyhow!("Invalid HTTP method '{}'", step.method))?;
+    let headers = step
+        .headers
+        .iter()
+        .map(|(name, value)| Ok((name.clone(), substitute(value, vars)?)))
+        .collect::<Result<Vec<_>>>()?;
+    let body = step
+        .body
+        .as_ref()
+        .map(|body| substitute_json(body, vars))
+        .transpose()?;
+
+    let response = client
+        .request(method, &url, &headers, body.as_ref(), step.timeout)
+        .await?;
+    let json: Option<Value> = serde_json::from_str(&response.body).ok();
+    let mut failures = check_expectations(
+        &step.expect,
+        response.status.as_u16(),
+        response.latency,
+        json.as_ref(),
+    );
+
+    for (var, path) in &step.capture {
+        match json.as_ref().and_then(|j| lookup(j, path)) {
+            Some(Value::String(s)) => {
+                vars.insert(var.clone(), s.clone());
+            }
+            Some(value) => {
+                vars.insert(var.clone(), value.to_string());
+            }
+            None => failures.push(format!("cannot capture {}: {} missing", var, path)),
+        }
+    }
+
+    Ok((response.status.as_u16(), response.latency, failures))
+}
+
+/// Work out the URL of a step from `url` or `service` + `path`
+fn target_url(step: &SyntheticStep) -> Result<String> {
+    match (&step.url, &step.service) {
+        (Some(url), None) => Ok(url.clone()),
+        (None, Some(service)) => {
+            let service = registry::resolve(service)?;
+            let base = Url::parse(service.endpoint)?;
+            let path = step.path.as_deref().unwrap_or("/");
+            Ok(base.join(path)?.to_string())
+        }
+        (Some(_), Some(_)) => bail!("Set either 'url' or 'service', not both"),
+        (None, None) => bail!("Missing 'url' or 'service'"),
+    }
+}
+
+/// Compare a response against the step's expectations
+fn check_expectations(
+    expect: &StepExpect,
+    status: u16,
+    latency: Duration,
+    json: Option<&Value>,
+) -> Vec<String> {
+    let mut failures = Vec::new();
+
+    match expect.status {
+        Some(expected) if expected != status => {
+            failures.push(format!("expected status {}, got {}", expected, status))
+        }
+        None if !(200..300).contains(&status) => {
+            failures.push(format!("expected a 2xx status, got {}", status))
+        }
+        _ => {}
+    }
+
+    if let Some(max) = expect.max_latency_ms {
+        let took = latency.as_millis() as u64;
+        if took > max {
+            failures.push(format!("took {}ms, limit is {}ms", took, max));
+        }
+    }
+
+    let needs_json =
+        !expect.exists.is_empty() || !expect.non_empty.is_empty() || !expect.equals.is_empty();
+    let Some(json) = json else {
+        if needs_json {
+            failures.push("response is not JSON".to_string());
+        }
+        return failures;
+    };
+
+    for path in &expect.exists {
+        if lookup(json, path).is_none() {
+            failures.push(format!("{} missing", path));
+        }
+    }
+
+    for path in &expect.non_empty {
+        match lookup(json, path) {
+            None => failures.push(format!("{} missing", path)),
+            Some(value) if is_empty(value) => failures.push(format!("{} is empty", path)),
+            Some(_) => {}
+        }
+    }
+
+    for (path, expected) in &expect.equals {
+        match lookup(json, path) {
+            None => failures.push(format!("{} missing", path)),
+            Some(value) if value != expected => {
+                failures.push(format!("{} is {}, expected {}", path, value, expected))
+            }
+            Some(_) => {}
+        }
+    }
+
+    failures
+}
+
+/// Whether a JSON value carries no content
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        Value::Array(a) => a.is_empty(),
+        Value::Object(o) => o.is_empty(),
+        _ => false,
+    }
+}
+
+/// Resolve a path like `choices[0].text`, `$.choices.0.text` or `id`
+fn lookup<'a>(json: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let normalized = path.replace('[', ".").replace(']', "");
+
+    normalized
+        .split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(json, |value, segment| match value {
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            Value::Object(map) => map.get(segment),
+            _ => None,
+        })
+}
+
+/// Replace `{{name}}` with captured values and `{{env.NAME}}` with the environment
+fn substitute(template: &str, vars: &HashMap<String, String>) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| anyhow!("Unclosed '{{{{' in '{}'", template))?;
+        let name = after[..end].trim();
+
+        let value = match name.strip_prefix("env.") {
+            Some(var) => std::env::var(var)
+                .map_err(|_| anyhow!("Environment variable {} is not set", var))?,
+            None => vars.get(name).cloned().ok_or_else(|| {
+                anyhow!("Variable '{}' was not captured by an earlier step", name)
+            })?,
+        };
+        out.push_str(&value);
+        rest = &after[end + 2..];
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Apply `substitute` to every string inside a JSON body
+fn substitute_json(value: &Value, vars: &HashMap<String, String>) -> Result<Value> {
+    Ok(match value {
+        Value::String(s) => Value::String(substitute(s, vars)?),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| substitute_json(item, vars))
+                .collect::<Result<_>>()?,
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| Ok((k.clone(), substitute_json(v, vars)?)))
+                .collect::<Result<_>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+/// Print one line per check, with step details for failures or in verbose mode
+pub(super) fn print_report(results: &[CheckResult], verbose: bool) {
+    println!();
+    println!("  {}", "Synthetic Checks:".cyan().bold());
+    println!("  {}", "─".repeat(60));
+
+    if results.is_empty() {
+        println!(
+            "  {} No checks configured (add [[status.synthetic]] to the config file)",
+            "○".dimmed()
+        );
+    }
+
+    for result in results {
+        let total: Duration = result.steps.iter().filter_map(|s| s.latency).sum();
+        if result.passed() {
+            println!(
+                "  {} {:<32} {} step(s)  {}",
+                "✓".green().bold(),
+                result.name,
+                result.total_steps,
+                format!("{}ms", total.as_millis()).dimmed()
+            );
+        } else {
+            println!(
+                "  {} {:<32} failed at step {}/{}",
+                "✖".red().bold(),
+                result.name.red(),
+                result.steps.len(),
+                result.total_steps
+            );
+        }
+
+        if verbose || !result.passed() {
+            for step in &result.steps {
+                let mark = if step.failures.is_empty() {
+                    "✓".green()
+                } else {
+                    "✖".red()
+                };
+                let status = step
+                    .status
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "---".to_string());
+                let latency = step
+                    .latency
+                    .map(|l| format!("{}ms", l.as_millis()))
+                    .unwrap_or_default();
+                println!(
+                    "      {} {:<24} {} {:>7}",
+                    mark,
+                    step.name,
+                    status,
+                    latency.dimmed()
+                );
+                for failure in &step.failures {
+                    println!("          {}", failure.red());
+                }
+            }
+        }
+    }
+
+    println!("  {}", "─".repeat(60));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_path_lookup() {
+        let body = json!({ "id": "req-1", "choices": [{ "text": "hi" }] });
+        assert_eq!(lookup(&body, "id"), Some(&json!("req-1")));
+        assert_eq!(lookup(&body, "choices[0].text"), Some(&json!("hi")));
+        assert_eq!(lookup(&body, "$.choices.0.text"), Some(&json!("hi")));
+        assert_eq!(lookup(&body, "choices[1].text"), None);
+    }
+
+    #[test]
+    fn test_expectations() {
+        let expect = StepExpect {
+            status: None,
+            max_latency_ms: Some(100),
+            exists: vec!["id".to_string()],
+            non_empty: vec!["choices[0].text".to_string()],
+            equals: [("model".to_string(), json!("vortex-v3"))].into(),
+        };
+        let good = json!({ "id": "x", "model": "vortex-v3", "choices": [{ "text": "ok" }] });
+        assert!(
+            check_expectations(&expect, 200, Duration::from_millis(50), Some(&good)).is_empty()
+        );
+
+        let bad = json!({ "model": "vortex-v2", "choices": [{ "text": " " }] });
+        let failures = check_expectations(&expect, 503, Duration::from_millis(250), Some(&bad));
+        assert_eq!(failures.len(), 5);
+    }
+
+    #[test]
+    fn test_substitution() {
+        let vars = HashMap::from([("job".to_string(), "42".to_string())]);
+        assert_eq!(substitute("/jobs/{{ job }}", &vars).unwrap(), "/jobs/42");
+        assert!(substitute("/jobs/{{missing}}", &vars).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! Dependency tree rendering and root-cause analysis
+//!
+//! Uses the `depends_on` declarations in the service registry to show
+//! how services are layered and to explain cascading failures.
+
+use crate::utils::registry::{find, Service, SERVICES};
+use colored::Colorize;
+use std::collections::HashSet;
+
+/// Expand a selection with everything it transitively depends on
+///
+/// The result keeps registry order so the scan output stays stable.
+pub(super) fn with_dependencies(selected: &[&'static Service]) -> Vec<&'static Service> {
+    let mut wanted: HashSet<&str> = HashSet::new();
+    let mut stack: Vec<&str> = selected.iter().map(|s| s.name).collect();
+
+    while let Some(name) = stack.pop() {
+        if wanted.insert(name) {
+            if let Some(service) = find(name) {
+                stack.extend(service.depends_on.iter().copied());
+            }
+        }
+    }
+
+    SERVICES
+        .iter()
+        .filter(|s| wanted.contains(s.name))
+        .collect()
+}
+
+/// A failed service together with the failed dependencies that explain it
+pub(super) struct RootCause {
+    pub service: &'static str,
+    pub causes: Vec<&'static str>,
+}
+
+/// Attribute every failed service to the deepest failed dependencies below it
+///
+/// Services whose own dependencies are all healthy are considered root causes
+/// and are returned with an empty `causes` list.
+pub(super) fn root_causes(checked: &[&Service], down: &HashSet<&str>) -> Vec<RootCause> {
+    let checked_names: HashSet<&str> = checked.iter().map(|s| s.name).collect();
+
+    checked
+        .iter()
+        .filter(|s| down.contains(s.name))
+        .map(|s| {
+            let mut causes = Vec::new();
+            collect_causes(s, down, &checked_names, &mut causes, &mut HashSet::new());
+            RootCause {
+                service: s.name,
+                causes,
+            }
+        })
+        .collect()
+}
+
+/// Walk failed dependencies depth-first, recording those with no failed dependency of their own
+fn collect_causes(
+    service: &Service,
+    down: &HashSet<&str>,
+    checked: &HashSet<&str>,
+    causes: &mut Vec<&'static str>,
+    visited: &mut HashSet<&'static str>,
+) {
+    for dep in service.depends_on {
+        if !checked.contains(dep) || !down.contains(dep) || !visited.insert(dep) {
+            continue;
+        }
+        let Some(dep_service) = find(dep) else {
+            continue;
+        };
+
+        let failed_below = dep_service
+            .depends_on
+            .iter()
+            .any(|d| checked.contains(d) && down.contains(d));
+
+        if failed_below {
+            collect_causes(dep_service, down, checked, causes, visited);
+        } else if !causes.contains(dep) {
+            causes.push(dep);
+        }
+    }
+}
+
+/// Print the dependency tree with the health of every node
+pub(super) fn print_tree(checked: &[&Service], down: &HashSet<&str>) {
+    let checked_names: HashSet<&str> = checked.iter().map(|s| s.name).collect();
+
+    // Roots are the services nothing else in the selection depends on
+    let roots: Vec<&&Service> = checked
+        .iter()
+        .filter(|s| {
+            !checked
+                .iter()
+                .any(|other| other.depends_on.contains(&s.name))
+        })
+        .collect();
+
+    println!("  {}", "DEPENDENCY TREE".cyan().bold());
+    println!("  {}", "─".repeat(60));
+
+    for root in roots {
+        println!("  {}", node_label(root, down));
+        print_children(root, down, &checked_names, "");
+    }
+
+    println!("  {}", "─".repeat(60));
+}
+
+/// Recursively print the dependencies of a node with box-drawing guides
+fn print_children(service: &Service, down: &HashSet<&str>, checked: &HashSet<&str>, prefix: &str) {
+    let children: Vec<&Service> = service
+        .depends_on
+        .iter()
+        .filter(|d| checked.contains(*d))
+        .filter_map(|d| find(d))
+        .collect();
+
+    for (i, child) in children.iter().enumerate() {
+        let last = i == children.len() - 1;
+        let branch = if last { "└── " } else { "├── " };
+        println!(
+            "  {}{}{}",
+            prefix.dimmed(),
+            branch.dimmed(),
+            node_label(child, down)
+        );
+
+        let next_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        print_children(child, down, checked, &next_prefix);
+    }
+}
+
+/// Format a tree node as icon, name and status indicator
+fn node_label(service: &Service, down: &HashSet<&str>) -> String {
+    let status = if down.contains(service.name) {
+        "[OFFLINE] ○".red().to_string()
+    } else {
+        "[ONLINE]  ●".green().to_string()
+    };
+    format!("{} {} {}", service.icon, service.name, status)
+}
+
+/// Print the root-cause section when failures cascade through dependencies
+pub(super) fn print_root_causes(checked: &[&Service], down: &HashSet<&str>) {
+    let analysis = root_causes(checked, down);
+    if analysis.iter().all(|rc| rc.causes.is_empty()) {
+        return;
+    }
+
+    println!();
+    println!("  {}", "Root Cause Analysis:".cyan().bold());
+
+    for rc in analysis.iter().filter(|rc| rc.causes.is_empty()) {
+        println!(
+            "    {} {} is unreachable {}",
+            "✖".red().bold(),
+            rc.service.red().bold(),
+            "(likely root cause)".dimmed()
+        );
+    }
+
+    for rc in analysis.iter().filter(|rc| !rc.causes.is_empty()) {
+        let verb = if rc.causes.len() == 1 { "is" } else { "are" };
+        println!(
+            "    {} {} down because {} {} unreachable",
+            "↳".dimmed(),
+            rc.service.yellow(),
+            rc.causes.join(" and ").red(),
+            verb
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all() -> Vec<&'static Service> {
+        SERVICES.iter().collect()
+    }
+
+    #[test]
+    fn test_dependency_expansion() {
+        let vortex = find("VORTEX").unwrap();
+        let names: Vec<&str> = with_dependencies(&[vortex])
+            .iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, ["KRONOS", "HERMES", "VORTEX"]);
+    }
+
+    #[test]
+    fn test_root_cause_points_to_deepest_failure() {
+        let down: HashSet<&str> = ["CERBERUS", "VORTEX", "HERMES"].into();
+        let analysis = root_causes(&all(), &down);
+
+        let cause_of = |name: &str| {
+            analysis
+                .iter()
+                .find(|rc| rc.service == name)
+                .map(|rc| rc.causes.clone())
+                .unwrap()
+        };
+
+        assert_eq!(cause_of("VORTEX"), ["HERMES"]);
+        assert_eq!(cause_of("CERBERUS"), ["HERMES"]);
+        assert!(cause_of("HERMES").is_empty());
+    }
+
+    #[test]
+    fn test_independent_failure_is_its_own_root() {
+        let down: HashSet<&str> = ["OPTICUS"].into();
+        let analysis = root_causes(&all(), &down);
+        assert_eq!(analysis.len(), 1);
+        assert!(analysis[0].causes.is_empty());
+    }
+}
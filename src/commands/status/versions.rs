@@ -0,0 +1,356 @@
+//! Cross-service version compatibility
+//!
+//! Collects the version and protocol each service reports from its health
+//! endpoint (falling back to `/info`) and checks them against the
+//! compatibility matrix: the rules shipped with the CLI plus any
+//! `[[status.compat]]` entries from the configuration file.
+
+use crate::utils::client::TitanClient;
+use crate::utils::config::CompatRule;
+use crate::utils::registry::{self, Service, PROTOCOL_VERSION};
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use reqwest::Url;
+use semver::{Version, VersionReq};
+use serde_json::Value;
+
+/// Version details reported by one service
+pub(super) struct Reported {
+    pub service: &'static Service,
+    pub raw: Option<String>,
+    pub version: Option<Version>,
+    pub protocol: Option<u32>,
+}
+
+/// A problem found while comparing versions
+#[derive(Debug, PartialEq)]
+pub(super) enum Finding {
+    /// The service speaks a different protocol than the CLI
+    Protocol {
+        service: &'static str,
+        reported: u32,
+    },
+    /// A peer does not satisfy a rule triggered by the service's version
+    Mismatch {
+        service: &'static str,
+        version: String,
+        peer: &'static str,
+        requirement: String,
+        found: String,
+    },
+    /// A rule applies but the peer's version is not known
+    Unverifiable {
+        service: &'static str,
+        peer: &'static str,
+    },
+}
+
+/// Compatibility rules shipped with the CLI
+fn builtin_rules() -> Vec<CompatRule> {
+    let rule = |service: &str, version: &str, requires: &[(&str, &str)]| CompatRule {
+        service: service.to_string(),
+        version: version.to_string(),
+        requires: requires
+            .iter()
+            .map(|(peer, req)| (peer.to_string(), req.to_string()))
+            .collect(),
+    };
+
+    vec![
+        rule(
+            "VORTEX",
+            ">=3.0.0",
+            &[("HERMES", ">=2.0.0"), ("KRONOS", ">=1.4.0")],
+        ),
+        rule("OPTICUS", ">=2.0.0", &[("HERMES", ">=2.0.0")]),
+        rule("CERBERUS", ">=2.0.0", &[("VORTEX", ">=3.0.0")]),
+    ]
+}
+
+/// Ask every service for its version, trying `/info` when health is silent
+pub(super) async fn collect(client: &TitanClient, services: &[&'static Service]) -> Vec<Reported> {
+    let mut reports = Vec::with_capacity(services.len());
+
+    for service in services {
+        let mut info = fetch_json(client, service.endpoint).await;
+        if info.as_ref().and_then(extract_version).is_none() {
+            if let Some(url) = Url::parse(service.endpoint)
+                .ok()
+                .and_then(|u| u.join("/info").ok())
+            {
+                info = fetch_json(client, url.as_str()).await.or(info);
+            }
+        }
+
+        let raw = info.as_ref().and_then(extract_version);
+        reports.push(Reported {
+            service,
+            version: raw.as_deref().and_then(parse_version),
+            protocol: info.as_ref().and_then(extract_protocol),
+            raw,
+        });
+    }
+
+    reports
+}
+
+/// Fetch a URL and parse the body as JSON, if possible
+async fn fetch_json(client: &TitanClient, url: &str) -> Option<Value> {
+    let body = client.get(url).await.ok()?;
+    serde_json::from_str(&body).ok()
+}
+
+/// Read `version` (or `build.version`) from a health or info document
+fn extract_version(info: &Value) -> Option<String> {
+    info.get("version")
+        .or_else(|| info.pointer("/build/version"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Read the protocol version, accepting `1`, `"1"`, `"v1"` or `"Titan Protocol v1"`
+fn extract_protocol(info: &Value) -> Option<u32> {
+    let value = info
+        .get("protocol")
+        .or_else(|| info.get("protocol_version"))?;
+    match value {
+        Value::Number(n) => n.as_u64().map(|n| n as u32),
+        Value::String(s) => {
+            let digits: String = s
+                .rsplit(|c: char| !c.is_ascii_digit())
+                .find(|part| !part.is_empty())?
+                .to_string();
+            digits.parse().ok()
+        }
+        _ => None,
+    }
+}
+
+/// Parse a reported version leniently (`v3`, `3.1`, `3.1.0-rc1`)
+pub(super) fn parse_version(raw: &str) -> Option<Version> {
+    let trimmed = raw.trim().trim_start_matches(['v', 'V']);
+    if let Ok(version) = Version::parse(trimmed) {
+        return Some(version);
+    }
+
+    let (core, suffix) = match trimmed.find(['-', '+']) {
+        Some(i) => trimmed.split_at(i),
+        None => (trimmed, ""),
+    };
+    let mut parts: Vec<&str> = core.split('.').collect();
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    Version::parse(&format!("{}{}", parts.join("."), suffix)).ok()
+}
+
+/// Check protocol versions and every applicable rule
+pub(super) fn evaluate(reports: &[Reported], extra_rules: &[CompatRule]) -> Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+
+    for report in reports {
+        if let Some(protocol) = report.protocol {
+            if protocol != PROTOCOL_VERSION {
+                findings.push(Finding::Protocol {
+                    service: report.service.name,
+                    reported: protocol,
+                });
+            }
+        }
+    }
+
+    let lookup = |name: &str| reports.iter().find(|r| r.service.name == name);
+
+    for rule in builtin_rules().iter().chain(extra_rules) {
+        let service = registry::find(&rule.service)
+            .ok_or_else(|| anyhow!("Unknown service '{}' in compat rule", rule.service))?;
+        let applies_to = VersionReq::parse(&rule.version).map_err(|e| {
+            anyhow!(
+                "Invalid version '{}' in compat rule for {}: {}",
+                rule.version,
+                rule.service,
+                e
+            )
+        })?;
+
+        let Some(version) = lookup(service.name).and_then(|r| r.version.as_ref()) else {
+            continue;
+        };
+        if !applies_to.matches(version) {
+            continue;
+        }
+
+        for (peer_name, requirement) in &rule.requires {
+            let peer = registry::find(peer_name)
+                .ok_or_else(|| anyhow!("Unknown service '{}' in compat rule", peer_name))?;
+            let req = VersionReq::parse(requirement).map_err(|e| {
+                anyhow!(
+                    "Invalid requirement '{}' for {} in compat rule: {}",
+                    requirement,
+                    peer_name,
+                    e
+                )
+            })?;
+
+            // Peers outside the current selection are not judged
+            let Some(peer_report) = lookup(peer.name) else {
+                continue;
+            };
+            match peer_report.version {
+                Some(ref found) if req.matches(found) => {}
+                Some(ref found) => findings.push(Finding::Mismatch {
+                    service: service.name,
+                    version: version.to_string(),
+                    peer: peer.name,
+                    requirement: requirement.clone(),
+                    found: found.to_string(),
+                }),
+                None => findings.push(Finding::Unverifiable {
+                    service: service.name,
+                    peer: peer.name,
+                }),
+            }
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Print reported versions followed by any compatibility findings
+pub(super) fn print_report(reports: &[Reported], findings: &[Finding]) {
+    println!();
+    println!("  {}", "Version Compatibility:".cyan().bold());
+    println!("  {}", "─".repeat(60));
+
+    for report in reports {
+        let name_display = format!("{} {}", report.service.icon, report.service.name);
+        let padding = 16usize.saturating_sub(name_display.chars().count());
+        // Padded before colouring, so escape codes do not count as width
+        let version = match report.raw {
+            Some(ref raw) => format!("{:<12}", raw).white().to_string(),
+            None => format!("{:<12}", "unknown").dimmed().to_string(),
+        };
+        let protocol = match report.protocol {
+            Some(p) if p == PROTOCOL_VERSION => format!("protocol v{}", p).dimmed().to_string(),
+            Some(p) => format!("protocol v{}", p).red().to_string(),
+            None => String::new(),
+        };
+        println!(
+            "  {}{}{} {}",
+            name_display,
+            " ".repeat(padding),
+            version,
+            protocol
+        );
+    }
+
+    println!("  {}", "─".repeat(60));
+
+    if findings.is_empty() {
+        println!(
+            "  {} Reported versions are compatible (CLI speaks protocol v{})",
+            "✓".green().bold(),
+            PROTOCOL_VERSION
+        );
+        return;
+    }
+
+    for finding in findings {
+        match finding {
+            Finding::Protocol { service, reported } => println!(
+                "  {} {} speaks protocol v{}, this CLI speaks v{}",
+                "✖".red().bold(),
+                service.red().bold(),
+                reported,
+                PROTOCOL_VERSION
+            ),
+            Finding::Mismatch {
+                service,
+                version,
+                peer,
+                requirement,
+                found,
+            } => println!(
+                "  {} {} {} requires {} {}, found {}",
+                "✖".red().bold(),
+                service.yellow(),
+                version,
+                peer.red().bold(),
+                requirement,
+                found.red()
+            ),
+            Finding::Unverifiable { service, peer } => println!(
+                "  {} Cannot verify {} against {}: version unknown",
+                "⚠".yellow().bold(),
+                service,
+                peer
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(name: &str, version: Option<&str>, protocol: Option<u32>) -> Reported {
+        Reported {
+            service: registry::find(name).unwrap(),
+            raw: version.map(str::to_string),
+            version: version.and_then(parse_version),
+            protocol,
+        }
+    }
+
+    #[test]
+    fn test_lenient_version_parsing() {
+        assert_eq!(parse_version("v3"), Some(Version::new(3, 0, 0)));
+        assert_eq!(parse_version("2.1"), Some(Version::new(2, 1, 0)));
+        assert!(parse_version("3.1-rc1").is_some());
+        assert!(parse_version("latest").is_none());
+    }
+
+    #[test]
+    fn test_new_vortex_against_old_hermes() {
+        let reports = [
+            report("VORTEX", Some("3.2.0"), Some(1)),
+            report("HERMES", Some("1.9.4"), Some(1)),
+            report("KRONOS", None, None),
+        ];
+        let findings = evaluate(&reports, &[]).unwrap();
+
+        assert!(findings.contains(&Finding::Mismatch {
+            service: "VORTEX",
+            version: "3.2.0".to_string(),
+            peer: "HERMES",
+            requirement: ">=2.0.0".to_string(),
+            found: "1.9.4".to_string(),
+        }));
+        assert!(findings.contains(&Finding::Unverifiable {
+            service: "VORTEX",
+            peer: "KRONOS",
+        }));
+    }
+
+    #[test]
+    fn test_protocol_mismatch_is_flagged() {
+        let reports = [report("OPTICUS", Some("1.0.0"), Some(2))];
+        let findings = evaluate(&reports, &[]).unwrap();
+        assert_eq!(
+            findings,
+            [Finding::Protocol {
+                service: "OPTICUS",
+                reported: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_protocol_strings() {
+        let info: Value = serde_json::json!({ "protocol": "Titan Protocol v1" });
+        assert_eq!(extract_protocol(&info), Some(1));
+    }
+}
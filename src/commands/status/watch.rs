@@ -0,0 +1,150 @@
+//! Watch mode
+//!
+//! Re-runs the status checks on an interval, redraws the table and fires
+//! the transition hooks configured under `[status.alerts]`.
+
+use super::hooks::{self, Decision, HealthState, Tracker, Transition};
+use super::StatusOptions;
+use crate::utils::client::TitanClient;
+use crate::utils::config::Config;
+use crate::utils::registry::Service;
+use crate::utils::ui;
+use anyhow::Result;
+use chrono::{Local, Utc};
+use colored::Colorize;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Number of recent events kept below the table
+const EVENT_LOG_SIZE: usize = 10;
+
+/// Watch the selected services until interrupted
+pub(super) async fn run(
+    opts: &StatusOptions,
+    config: &Config,
+    selected: &[&'static Service],
+    verbose: bool,
+) -> Result<()> {
+    let alerts = &config.status.alerts;
+    let alert_hooks = hooks::load(&alerts.hooks)?;
+
+    let client = TitanClient::new()?;
+    let context = config.context();
+    let mut tracker = Tracker::new(alerts);
+    let mut events: VecDeque<String> = VecDeque::new();
+    let mut ticker = tokio::time::interval(Duration::from_secs(opts.interval.max(1)));
+    let term = console::Term::stdout();
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+
+        let results = tokio::select! {
+            results = super::check_services(&client, selected, false) => results,
+            _ = tokio::signal::ctrl_c() => break,
+        };
+        super::record_history(config, &results, verbose);
+
+        let now = Instant::now();
+        for (service, result) in &results {
+            let state = HealthState::of(result);
+            let stamp = Local::now().format("%H:%M:%S").to_string();
+
+            match tracker.observe(service.name, state, now) {
+                Decision::Fire(from) => {
+                    let transition = Transition {
+                        service: service.name,
+                        from,
+                        to: state,
+                        timestamp: Utc::now(),
+                        context: context.clone(),
+                        http_status: result.as_ref().ok().map(|p| p.status.as_u16()),
+                        error: result.as_ref().err().map(|e| e.to_string()),
+                    };
+                    push_event(
+                        &mut events,
+                        format!(
+                            "{} {} {} → {}",
+                            stamp.dimmed(),
+                            service.name.bold(),
+                            from.as_str(),
+                            colorize(state)
+                        ),
+                    );
+                    for line in hooks::fire(&alert_hooks, &transition, &client).await {
+                        push_event(
+                            &mut events,
+                            format!("{}   {}", stamp.dimmed(), line.dimmed()),
+                        );
+                    }
+                }
+                Decision::FlappingStarted => push_event(
+                    &mut events,
+                    format!(
+                        "{} {} {}",
+                        stamp.dimmed(),
+                        service.name.bold(),
+                        "is flapping, holding back actions".yellow()
+                    ),
+                ),
+                Decision::Settled => push_event(
+                    &mut events,
+                    format!(
+                        "{} {} stopped flapping, still {}",
+                        stamp.dimmed(),
+                        service.name.bold(),
+                        colorize(state)
+                    ),
+                ),
+                Decision::Suppressed | Decision::Nothing => {}
+            }
+        }
+
+        let _ = term.clear_screen();
+        println!();
+        ui::print_header("TITAN PROTOCOL STATUS");
+        println!();
+        super::print_results(opts, selected, &results, verbose);
+        super::print_summary(&results);
+
+        if !events.is_empty() {
+            println!("  {}", "Recent Transitions:".cyan().bold());
+            for event in &events {
+                println!("    {}", event);
+            }
+            println!();
+        }
+
+        println!(
+            "  {} Last check {} · every {}s · {} hook(s) · Ctrl+C to stop",
+            "⏱".dimmed(),
+            Local::now().format("%H:%M:%S"),
+            opts.interval.max(1),
+            alerts.hooks.len()
+        );
+    }
+
+    println!();
+    println!("  {} Watch stopped", "⏹".yellow());
+    println!();
+    Ok(())
+}
+
+/// Append to the event log, dropping the oldest entries
+fn push_event(events: &mut VecDeque<String>, event: String) {
+    events.push_back(event);
+    while events.len() > EVENT_LOG_SIZE {
+        events.pop_front();
+    }
+}
+
+/// Color a state name by severity
+fn colorize(state: HealthState) -> String {
+    match state {
+        HealthState::Healthy => state.as_str().green().to_string(),
+        HealthState::Degraded => state.as_str().yellow().to_string(),
+        HealthState::Down => state.as_str().red().to_string(),
+    }
+}
@@ -0,0 +1,102 @@
+//! Templates command implementation
+//!
+//! Lists, shows and creates the prompt templates used by
+//! `titan ask --template`.
+
+use crate::utils::templates;
+use crate::utils::ui;
+use anyhow::Result;
+use colored::Colorize;
+
+/// Print every template with its variables
+pub fn list() -> Result<()> {
+    let templates = templates::list()?;
+
+    println!();
+    ui::print_header("PROMPT TEMPLATES");
+    println!();
+
+    if templates.is_empty() {
+        println!(
+            "  {} No templates yet (create one with titan templates new <name>)",
+            "○".dimmed()
+        );
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "  {:<20} {:<8} {:<24} {}",
+        "TEMPLATE".cyan().bold(),
+        "SOURCE".cyan().bold(),
+        "VARIABLES".cyan().bold(),
+        "DESCRIPTION".cyan().bold()
+    );
+    println!("  {}", "─".repeat(76));
+    for template in &templates {
+        println!(
+            "  {:<20} {:<8} {:<24} {}",
+            template.name,
+            template.source.to_string(),
+            template.variables().join(", "),
+            template.description.as_deref().unwrap_or_default().dimmed()
+        );
+    }
+    println!("  {}", "─".repeat(76));
+    println!();
+    Ok(())
+}
+
+/// Print a template's prompt and variables
+pub fn show(name: &str) -> Result<()> {
+    let template = templates::load(name)?;
+
+    println!();
+    ui::print_header(&format!("TEMPLATE {}", template.name.to_uppercase()));
+    println!();
+    if let Some(ref description) = template.description {
+        println!("  {} {}", "Description:".dimmed(), description);
+    }
+    println!(
+        "  {} {} ({})",
+        "File:".dimmed(),
+        template.path.display(),
+        template.source
+    );
+    if let Some(ref model) = template.model {
+        println!("  {} {}", "Model:".dimmed(), model);
+    }
+
+    let variables = template.variables();
+    if !variables.is_empty() {
+        println!("  {}", "Variables:".dimmed());
+        for name in &variables {
+            match template.defaults.get(name) {
+                Some(default) => println!("    {} (default: {})", name, default),
+                None => println!("    {} {}", name, "(required)".yellow()),
+            }
+        }
+    }
+
+    println!();
+    for line in template.prompt.trim().lines() {
+        println!("    {}", line);
+    }
+    println!();
+    Ok(())
+}
+
+/// Create a template skeleton to edit
+pub fn new(name: &str, project: bool, force: bool) -> Result<()> {
+    let path = templates::create(name, project, force)?;
+    ui::print_success(&format!("Created {}", path.display()));
+    println!(
+        "  {}",
+        format!(
+            "Edit the prompt, then run: titan ask --template {} --var name=value",
+            name
+        )
+        .dimmed()
+    );
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! Usage command implementation
+//!
+//! Summarizes the VORTEX usage ledger by model or by day, and shows how
+//! much of today's budget is left.
+
+use crate::utils::config::Config;
+use crate::utils::usage::{self, Totals};
+use crate::utils::{duration, ui};
+use anyhow::Result;
+use chrono::{Local, Utc};
+use colored::Colorize;
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+/// Groupings for `titan usage --by`
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum GroupBy {
+    Model,
+    Day,
+}
+
+/// Print token counts and spend recorded within `since`
+pub fn execute(since: Duration, by: GroupBy) -> Result<()> {
+    let config = Config::load()?;
+    let currency = &config.usage.currency;
+    let all = usage::load()?;
+    let cutoff = Utc::now() - chrono::Duration::from_std(since)?;
+    let records: Vec<&usage::Record> = all.iter().filter(|r| r.timestamp >= cutoff).collect();
+
+    println!();
+    ui::print_header("VORTEX USAGE");
+    println!();
+    println!(
+        "  {} last {} (since {})",
+        "Period:".dimmed(),
+        duration::humanize(since),
+        cutoff.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+    );
+    println!();
+
+    if records.is_empty() {
+        println!("  {} No VORTEX calls recorded in this period", "○".dimmed());
+    } else {
+        let (label, groups) = match by {
+            GroupBy::Model => (
+                "MODEL",
+                usage::summarize(records.iter().copied(), |r| r.model.clone()),
+            ),
+            GroupBy::Day => (
+                "DAY",
+                usage::summarize(records.iter().copied(), usage::day_of),
+            ),
+        };
+
+        println!(
+            "  {:<20} {:>6} {:>12} {:>12} {:>14}",
+            label.cyan().bold(),
+            "CALLS".cyan().bold(),
+            "PROMPT".cyan().bold(),
+            "COMPLETION".cyan().bold(),
+            "COST".cyan().bold()
+        );
+        println!("  {}", "─".repeat(68));
+
+        let mut total = Totals::default();
+        for (key, totals) in &groups {
+            print_row(key, totals, currency);
+            total.merge(totals);
+        }
+        println!("  {}", "─".repeat(68));
+        print_row("TOTAL", &total, currency);
+
+        if total.estimated > 0 {
+            println!();
+            println!(
+                "  {}",
+                format!(
+                    "~ {} call(s) had token counts estimated because VORTEX did not report them",
+                    total.estimated
+                )
+                .dimmed()
+            );
+        }
+        if total.unpriced > 0 {
+            let unpriced: BTreeSet<&str> = records
+                .iter()
+                .filter(|r| r.cost.is_none())
+                .map(|r| r.model.as_str())
+                .collect();
+            let names: Vec<&str> = unpriced.into_iter().collect();
+            println!(
+                "  {}",
+                format!(
+                    "No price in [usage.prices] for {}; their calls count as free",
+                    names.join(", ")
+                )
+                .dimmed()
+            );
+        }
+    }
+
+    if let Some(limit) = config.usage.daily_budget {
+        let spent = usage::spent_today(&all);
+        let line = format!(
+            "Today: {:.2} of {:.2} {} daily budget",
+            spent, limit, currency
+        );
+        println!();
+        if spent >= limit {
+            ui::print_warning(&line);
+        } else {
+            ui::print_info(&line);
+        }
+    }
+
+    println!();
+    Ok(())
+}
+
+fn print_row(key: &str, totals: &Totals, currency: &str) {
+    let cost = if totals.unpriced == totals.calls {
+        "-".to_string()
+    } else {
+        format!("{:.4} {}", totals.cost, currency)
+    };
+    println!(
+        "  {:<20} {:>6} {:>12} {:>12} {:>14}",
+        key, totals.calls, totals.prompt_tokens, totals.completion_tokens, cost
+    );
+}
@@ -10,12 +10,14 @@
 
 use clap::{Parser, Subcommand};
 use colored::Colorize;
+use std::io::IsTerminal;
+use std::path::PathBuf;
 use std::process::ExitCode;
 
 mod commands;
 mod utils;
 
-use commands::{ask, deploy, status, vision};
+use commands::{ask, cache, deploy, models, sessions, status, templates, usage, vision};
 
 /// ASCII Art Banner for TITAN-CLI
 const BANNER: &str = r#"
@@ -61,12 +63,36 @@ struct Cli {
     command: Commands,
 }
 
+/// `ask` arguments that only apply to a single query or chat, not to `--batch`
+///
+/// The batch settings repeat these conflicts because clap skips their
+/// `requires = "batch"` when `--batch` itself conflicts with a given argument.
+const SINGLE_QUERY_ARGS: [&str; 12] = [
+    "query",
+    "interactive",
+    "session",
+    "files",
+    "template",
+    "stream",
+    "tools",
+    "with_status",
+    "with_deploys",
+    "compare",
+    "judge",
+    "output",
+];
+
 /// Available commands for the Titan Protocol CLI
+// Parsed once per run, so the size of the `ask` variant does not matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// 📊 Check status of all Titan Protocol services
-    #[command(aliases = ["st", "health", "ping"])]
+    #[command(aliases = ["st", "health", "ping"], args_conflicts_with_subcommands = true)]
     Status {
+        #[command(subcommand)]
+        action: Option<StatusAction>,
+
         /// Check a specific service only
         #[arg(short, long)]
         service: Option<String>,
@@ -74,21 +100,259 @@ enum Commands {
         /// Show detailed information
         #[arg(short, long)]
         detailed: bool,
+
+        /// Render services as a dependency tree
+        #[arg(short, long)]
+        tree: bool,
+
+        /// Break checks into DNS, TCP, TLS and first-byte phases
+        #[arg(long)]
+        diagnose: bool,
+
+        /// Warn when a certificate expires within this many days
+        #[arg(long, value_name = "DAYS", requires = "diagnose")]
+        cert_warn_days: Option<i64>,
+
+        /// Run as a Prometheus exporter listening on ADDR (e.g. 127.0.0.1:9184)
+        #[arg(long, value_name = "ADDR")]
+        serve_metrics: Option<String>,
+
+        /// Check reported versions against the compatibility matrix
+        #[arg(long)]
+        versions: bool,
+
+        /// Run the synthetic end-to-end checks from the config file
+        #[arg(long)]
+        synthetic: bool,
+
+        /// Keep re-checking and run alert hooks on state changes
+        #[arg(short, long, conflicts_with_all = ["serve_metrics", "diagnose", "versions", "synthetic"])]
+        watch: bool,
+
+        /// Seconds between checks when running continuously
+        #[arg(long, value_name = "SECS", default_value = "15")]
+        interval: u64,
     },
 
     /// 🧠 Send a query to VORTEX AI Engine
     #[command(aliases = ["query", "q", "ai"])]
     Ask {
-        /// The query to send to VORTEX
-        query: String,
+        /// The query to send to VORTEX (piped stdin is attached as context)
+        query: Option<String>,
 
-        /// Use streaming response mode
+        /// Print tokens as they arrive
         #[arg(short, long)]
         stream: bool,
 
-        /// Specify model to use
-        #[arg(short, long, default_value = "vortex-v3")]
-        model: String,
+        /// Model ID or alias to use [default: the session's model, [ask] model or vortex-v3]
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Start an interactive multi-turn chat
+        #[arg(short, long, conflicts_with = "query")]
+        interactive: bool,
+
+        /// Continue the named conversation, creating it if needed
+        #[arg(long, value_name = "ID")]
+        session: Option<String>,
+
+        /// Attach a text file as context (repeatable)
+        #[arg(
+            short,
+            long = "file",
+            value_name = "PATH",
+            conflicts_with = "interactive"
+        )]
+        files: Vec<String>,
+
+        /// Never read stdin, even when it is not a terminal
+        #[arg(long)]
+        no_stdin: bool,
+
+        /// Print replies as plain text instead of rendered Markdown
+        #[arg(long)]
+        raw: bool,
+
+        /// Fill in a stored prompt template (see `titan templates`)
+        #[arg(short, long, value_name = "NAME", conflicts_with = "interactive")]
+        template: Option<String>,
+
+        /// Template variable (repeatable)
+        #[arg(
+            long = "var",
+            value_name = "NAME=VALUE",
+            requires = "template",
+            value_parser = utils::templates::parse_var
+        )]
+        vars: Vec<(String, String)>,
+
+        /// Print only the reply: no header, spinner or separators
+        #[arg(short, long, conflicts_with = "interactive")]
+        quiet: bool,
+
+        /// Print the reply as text or as a JSON object with usage details
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value = "text",
+            conflicts_with = "interactive"
+        )]
+        output: ask::OutputFormat,
+
+        /// Attach the current health of every service
+        #[arg(long, conflicts_with = "interactive")]
+        with_status: bool,
+
+        /// Attach recent deployments (window set by [ask] deploy_window)
+        #[arg(long, conflicts_with = "interactive")]
+        with_deploys: bool,
+
+        /// Let VORTEX call read-only titan tools, with approval
+        #[arg(long)]
+        tools: bool,
+
+        /// Sampling temperature, 0 to 2 [default: [ask] temperature]
+        #[arg(long, value_name = "T")]
+        temperature: Option<f64>,
+
+        /// Nucleus sampling probability mass, above 0 and at most 1
+        #[arg(long, value_name = "P")]
+        top_p: Option<f64>,
+
+        /// Most tokens to generate, within the model's output limit
+        #[arg(long, value_name = "N")]
+        max_tokens: Option<u64>,
+
+        /// Stop generating at this sequence (repeatable, up to 4)
+        #[arg(long, value_name = "TEXT")]
+        stop: Vec<String>,
+
+        /// Seed for reproducible sampling
+        #[arg(long, value_name = "N")]
+        seed: Option<u64>,
+
+        /// System prompt, replacing the session's
+        #[arg(long, value_name = "TEXT")]
+        system: Option<String>,
+
+        /// Read the system prompt from a file
+        #[arg(long, value_name = "PATH", conflicts_with = "system")]
+        system_file: Option<PathBuf>,
+
+        /// Run every prompt of a JSONL file and write the results as JSONL
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with_all = SINGLE_QUERY_ARGS
+        )]
+        batch: Option<PathBuf>,
+
+        /// Results file for --batch [default: <input>.results.jsonl]
+        #[arg(
+            long,
+            value_name = "PATH",
+            requires = "batch",
+            conflicts_with_all = SINGLE_QUERY_ARGS
+        )]
+        results: Option<PathBuf>,
+
+        /// Batch requests in flight at once
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 4,
+            value_parser = clap::value_parser!(u16).range(1..=64),
+            requires = "batch",
+            conflicts_with_all = SINGLE_QUERY_ARGS
+        )]
+        concurrency: u16,
+
+        /// Extra attempts for a batch prompt that failed with a transient error
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 2,
+            requires = "batch",
+            conflicts_with_all = SINGLE_QUERY_ARGS
+        )]
+        retries: u32,
+
+        /// Continue an interrupted batch, skipping prompts already answered
+        #[arg(long, requires = "batch", conflicts_with_all = SINGLE_QUERY_ARGS)]
+        resume: bool,
+
+        /// Send the query to several models at once and show the answers side by side
+        #[arg(
+            long,
+            value_name = "MODELS",
+            value_delimiter = ',',
+            conflicts_with_all = ["model", "interactive", "session", "stream", "tools"]
+        )]
+        compare: Vec<String>,
+
+        /// Have this model score the --compare answers
+        #[arg(long, value_name = "MODEL", requires = "compare")]
+        judge: Option<String>,
+
+        /// Always ask VORTEX, even with [cache] enabled
+        #[arg(long)]
+        no_cache: bool,
+    },
+
+    /// 🧩 List the models VORTEX serves
+    #[command(alias = "model")]
+    Models {
+        /// Fetch the list again instead of using the cache
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// 💰 Summarize VORTEX token usage and spend
+    #[command(aliases = ["cost", "spend"])]
+    Usage {
+        /// How far back to report (e.g. 24h, 7d, 30d)
+        #[arg(long, default_value = "30d", value_parser = utils::duration::parse)]
+        since: std::time::Duration,
+
+        /// Group calls by model or by day
+        #[arg(long, value_enum, default_value = "model")]
+        by: usage::GroupBy,
+    },
+
+    /// 📝 Manage prompt templates for `titan ask --template`
+    #[command(alias = "tpl")]
+    Templates {
+        #[command(subcommand)]
+        action: TemplatesAction,
+    },
+
+    /// 💬 Manage stored VORTEX conversations
+    #[command(aliases = ["session", "conv"])]
+    Sessions {
+        #[command(subcommand)]
+        action: SessionsAction,
+    },
+
+    /// 🗄️ Inspect or clear the local VORTEX response cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// 🩺 Ask VORTEX why the last failed command failed
+    ExplainLast {
+        /// Model ID or alias to use [default: [ask] model or vortex-v3]
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Only print the recorded failure; send nothing
+        #[arg(long)]
+        show: bool,
+
+        /// Print the explanation as plain text instead of rendered Markdown
+        #[arg(long)]
+        raw: bool,
     },
 
     /// 🚀 Deploy services to the Titan infrastructure
@@ -135,6 +399,98 @@ enum Commands {
     Version,
 }
 
+/// Subcommands of `titan status`
+#[derive(Subcommand)]
+enum StatusAction {
+    /// 📈 Show uptime history recorded by previous status runs
+    History {
+        /// Report on a specific service only
+        #[arg(short, long)]
+        service: Option<String>,
+
+        /// How far back to report (e.g. 30m, 24h, 7d)
+        #[arg(long, default_value = "24h", value_parser = utils::duration::parse)]
+        since: std::time::Duration,
+    },
+}
+
+/// Subcommands of `titan templates`
+#[derive(Subcommand)]
+enum TemplatesAction {
+    /// 📋 List project and personal templates
+    #[command(alias = "ls")]
+    List,
+
+    /// 📖 Print a template's prompt and variables
+    Show {
+        /// Template name
+        name: String,
+    },
+
+    /// ✏️ Create a template skeleton to edit
+    New {
+        /// Template name
+        name: String,
+
+        /// Store it in .titan/templates/ of the current project
+        #[arg(long)]
+        project: bool,
+
+        /// Overwrite an existing template
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Subcommands of `titan sessions`
+#[derive(Subcommand)]
+enum SessionsAction {
+    /// 📋 List stored sessions
+    #[command(alias = "ls")]
+    List,
+
+    /// 📖 Print a session's transcript
+    Show {
+        /// Session ID
+        id: String,
+    },
+
+    /// 📤 Export a session for a postmortem or ticket
+    Export {
+        /// Session ID
+        id: String,
+
+        /// Transcript format
+        #[arg(short, long, value_enum, default_value = "markdown")]
+        format: sessions::ExportFormat,
+
+        /// Write to a file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+
+    /// 🗑️ Delete a session
+    #[command(alias = "rm")]
+    Delete {
+        /// Session ID
+        id: String,
+
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+/// Subcommands of `titan cache`
+#[derive(Subcommand)]
+enum CacheAction {
+    /// 📊 Show entries, size and hits
+    Stats,
+
+    /// 🧹 Delete every cached response
+    Clear,
+}
+
 #[tokio::main]
 async fn main() -> ExitCode {
     // Load environment variables
@@ -153,16 +509,139 @@ async fn main() -> ExitCode {
         print_banner();
     }
 
+    // A failing explain-last must not replace the failure it explains
+    let explaining = matches!(cli.command, Commands::ExplainLast { .. });
+
     // Execute the appropriate command
     let result = match cli.command {
-        Commands::Status { service, detailed } => {
-            status::execute(service, detailed, cli.verbose).await
+        Commands::Status {
+            action: Some(StatusAction::History { service, since }),
+            ..
+        } => status::show_history(service, since),
+        Commands::Status {
+            action: None,
+            service,
+            detailed,
+            tree,
+            diagnose,
+            cert_warn_days,
+            serve_metrics,
+            versions,
+            synthetic,
+            watch,
+            interval,
+        } => {
+            let opts = status::StatusOptions {
+                service,
+                detailed,
+                tree,
+                diagnose,
+                cert_warn_days,
+                serve_metrics,
+                versions,
+                synthetic,
+                watch,
+                interval,
+            };
+            status::execute(opts, cli.verbose).await
         }
         Commands::Ask {
             query,
             stream,
             model,
-        } => ask::execute(&query, stream, &model, cli.verbose).await,
+            interactive,
+            session,
+            files,
+            no_stdin,
+            raw,
+            template,
+            vars,
+            quiet,
+            output,
+            with_status,
+            with_deploys,
+            tools,
+            temperature,
+            top_p,
+            max_tokens,
+            stop,
+            seed,
+            system,
+            system_file,
+            batch,
+            results,
+            concurrency,
+            retries,
+            resume,
+            compare,
+            judge,
+            no_cache,
+        } => {
+            let opts = ask::AskOptions {
+                query,
+                stream,
+                model,
+                interactive,
+                session,
+                files,
+                read_stdin: !no_stdin,
+                raw,
+                template,
+                vars,
+                quiet,
+                output,
+                with_status,
+                with_deploys,
+                tools,
+                params: utils::vortex::Params {
+                    temperature,
+                    top_p,
+                    max_tokens,
+                    stop,
+                    seed,
+                },
+                system,
+                system_file,
+                batch: batch.map(|input| ask::BatchOptions {
+                    input,
+                    results,
+                    concurrency: concurrency as usize,
+                    retries,
+                    resume,
+                    cache: !no_cache,
+                }),
+                compare,
+                judge,
+                no_cache,
+            };
+            ask::execute(opts, cli.verbose).await
+        }
+        Commands::Models { refresh } => models::execute(refresh, cli.verbose).await,
+        Commands::Usage { since, by } => usage::execute(since, by),
+        Commands::Templates { action } => match action {
+            TemplatesAction::List => templates::list(),
+            TemplatesAction::Show { name } => templates::show(&name),
+            TemplatesAction::New {
+                name,
+                project,
+                force,
+            } => templates::new(&name, project, force),
+        },
+        Commands::Sessions { action } => match action {
+            SessionsAction::List => sessions::list(),
+            SessionsAction::Show { id } => sessions::show(&id),
+            SessionsAction::Export { id, format, output } => {
+                sessions::export(&id, format, output.as_deref())
+            }
+            SessionsAction::Delete { id, yes } => sessions::delete(&id, yes),
+        },
+        Commands::Cache { action } => match action {
+            CacheAction::Stats => cache::stats(),
+            CacheAction::Clear => cache::clear(),
+        },
+        Commands::ExplainLast { model, show, raw } => {
+            ask::explain_last(model, show, raw, cli.verbose).await
+        }
         Commands::Deploy { service, env, yes } => {
             deploy::execute(&service, &env, yes, cli.verbose).await
         }
@@ -179,11 +658,29 @@ async fn main() -> ExitCode {
         Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
             eprintln!("{} {}", "✖ Error:".red().bold(), e);
+            if !explaining {
+                record_failure(&e);
+            }
             ExitCode::FAILURE
         }
     }
 }
 
+/// Keep the failure for `titan explain-last`
+///
+/// The command has already failed; a record that cannot be written is not
+/// worth a second error.
+fn record_failure(error: &anyhow::Error) {
+    let args: Vec<String> = std::env::args().collect();
+    let failure = utils::last_error::Failure::new(&args, error);
+    if utils::last_error::record(&failure).is_ok() && std::io::stderr().is_terminal() {
+        eprintln!(
+            "  {}",
+            "Run `titan explain-last` for likely causes and fixes".dimmed()
+        );
+    }
+}
+
 /// Print the TITAN banner
 fn print_banner() {
     println!("{}", BANNER.cyan());
@@ -198,7 +695,11 @@ fn print_version_info() {
         "Layer:".dimmed(),
         "L5 - Interface Layer".yellow()
     );
-    println!("  {} {}", "Protocol:".dimmed(), "Titan Protocol v1".white());
+    println!(
+        "  {} {}",
+        "Protocol:".dimmed(),
+        format!("Titan Protocol v{}", utils::registry::PROTOCOL_VERSION).white()
+    );
     println!("  {} {}", "Runtime:".dimmed(), "Tokio Async".white());
     println!("  {} {}", "Platform:".dimmed(), std::env::consts::OS);
     println!();
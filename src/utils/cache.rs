@@ -0,0 +1,305 @@
+//! Local cache of VORTEX responses
+//!
+//! With `[cache] enabled = true`, `titan ask` keeps each answer in
+//! `~/.titan/cache/responses/`, keyed by a SHA-256 hash of the VORTEX endpoint, the
+//! active context, the model, the sampling settings and every message sent
+//! (system prompt included). An identical query within the TTL is answered
+//! from disk without a request.
+//! Once the cache outgrows `max_size_mb`, the least recently used entries go.
+
+use super::config::{self, CacheConfig};
+use super::vortex::{Completion, Message, Params, Usage};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Bumped whenever the key material or entry layout changes
+const FORMAT: u32 = 2;
+
+/// A cached answer
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Entry {
+    /// When the answer was received from VORTEX
+    pub created: DateTime<Utc>,
+    /// Times the answer has been served from the cache
+    #[serde(default)]
+    pub hits: u64,
+    pub id: Option<String>,
+    pub model: String,
+    pub text: String,
+    pub finish_reason: Option<String>,
+    pub usage: Option<Usage>,
+}
+
+impl Entry {
+    /// The answer as a completion, as if it had just arrived
+    pub fn completion(&self, latency: Duration) -> Completion {
+        Completion {
+            id: self.id.clone(),
+            model: self.model.clone(),
+            text: self.text.clone(),
+            finish_reason: self.finish_reason.clone(),
+            usage: self.usage.clone(),
+            tool_calls: Vec::new(),
+            latency,
+            time_to_first_token: None,
+            interrupted: None,
+            chunks: 0,
+        }
+    }
+}
+
+/// What `titan cache stats` reports
+#[derive(Debug, Default)]
+pub struct Stats {
+    pub entries: u64,
+    /// Entries past the TTL, removed on next use or prune
+    pub expired: u64,
+    pub bytes: u64,
+    pub hits: u64,
+    /// Completion tokens not generated thanks to hits
+    pub saved_tokens: u64,
+    pub oldest: Option<DateTime<Utc>>,
+    pub newest: Option<DateTime<Utc>>,
+}
+
+/// Hash identifying a request to the VORTEX at `endpoint` in `context`
+pub fn key(
+    endpoint: &str,
+    context: &str,
+    model: &str,
+    params: &Params,
+    messages: &[Message],
+) -> String {
+    let material = serde_json::json!({
+        "format": FORMAT,
+        "endpoint": endpoint,
+        "context": context,
+        "model": model,
+        "params": params,
+        "messages": messages,
+    });
+    let digest = Sha256::digest(material.to_string().as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Directory holding cached responses
+pub fn dir() -> PathBuf {
+    config::titan_dir().join("cache").join("responses")
+}
+
+fn entry_path(key: &str) -> PathBuf {
+    dir().join(format!("{}.json", key))
+}
+
+/// Look up a request, counting a hit when a fresh answer is found
+pub fn get(config: &CacheConfig, key: &str) -> Result<Option<Entry>> {
+    let path = entry_path(key);
+    let mut entry = match read_entry(&path)? {
+        Some(entry) => entry,
+        None => return Ok(None),
+    };
+    if is_expired(&entry, config.ttl) {
+        remove(&path)?;
+        return Ok(None);
+    }
+
+    // Rewriting the entry also marks it as recently used for pruning
+    entry.hits += 1;
+    write_entry(&path, &entry)?;
+    Ok(Some(entry))
+}
+
+/// Store an answer; interrupted replies and tool requests are not cached
+pub fn put(key: &str, completion: &Completion) -> Result<()> {
+    if completion.interrupted.is_some() || !completion.tool_calls.is_empty() {
+        return Ok(());
+    }
+    let entry = Entry {
+        created: Utc::now(),
+        hits: 0,
+        id: completion.id.clone(),
+        model: completion.model.clone(),
+        text: completion.text.clone(),
+        finish_reason: completion.finish_reason.clone(),
+        usage: completion.usage.clone(),
+    };
+    fs::create_dir_all(dir()).with_context(|| format!("Failed to create {}", dir().display()))?;
+    write_entry(&entry_path(key), &entry)
+}
+
+/// Remove expired entries, then the least recently used ones until the
+/// cache fits `max_size_mb`
+pub fn prune(config: &CacheConfig) -> Result<()> {
+    let mut kept = Vec::new();
+    let mut total = 0;
+    for (path, entry, size, used) in scan()? {
+        match entry {
+            Some(ref entry) if !is_expired(entry, config.ttl) => {
+                total += size;
+                kept.push((path, size, used));
+            }
+            _ => remove(&path)?,
+        }
+    }
+
+    let limit = config.max_size_mb * 1024 * 1024;
+    kept.sort_by_key(|(_, _, used)| *used);
+    for (path, size, _) in kept {
+        if total <= limit {
+            break;
+        }
+        remove(&path)?;
+        total -= size;
+    }
+    Ok(())
+}
+
+/// Count entries, size and hits
+pub fn stats(config: &CacheConfig) -> Result<Stats> {
+    let mut stats = Stats::default();
+    for (_, entry, size, _) in scan()? {
+        stats.entries += 1;
+        stats.bytes += size;
+        let Some(entry) = entry else {
+            continue;
+        };
+        if is_expired(&entry, config.ttl) {
+            stats.expired += 1;
+        }
+        stats.hits += entry.hits;
+        let tokens = entry.usage.as_ref().map_or(0, |u| u.completion_tokens);
+        stats.saved_tokens += entry.hits * tokens;
+        stats.oldest = Some(stats.oldest.map_or(entry.created, |t| t.min(entry.created)));
+        stats.newest = Some(stats.newest.map_or(entry.created, |t| t.max(entry.created)));
+    }
+    Ok(stats)
+}
+
+/// Delete every entry, returning how many there were
+pub fn clear() -> Result<usize> {
+    let entries = scan()?;
+    for (path, ..) in &entries {
+        remove(path)?;
+    }
+    Ok(entries.len())
+}
+
+/// Every entry file with its contents (`None` if unreadable), size and
+/// last modification
+type Scanned = (PathBuf, Option<Entry>, u64, SystemTime);
+
+fn scan() -> Result<Vec<Scanned>> {
+    let dir = dir();
+    let listing = match fs::read_dir(&dir) {
+        Ok(listing) => listing,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+
+    let mut entries = Vec::new();
+    for item in listing {
+        let path = item?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        // Another titan process may prune the same entry meanwhile
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        let entry = read_entry(&path).ok().flatten();
+        let used = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        entries.push((path, entry, metadata.len(), used));
+    }
+    Ok(entries)
+}
+
+fn is_expired(entry: &Entry, ttl: Duration) -> bool {
+    let age = Utc::now().signed_duration_since(entry.created);
+    age.to_std().is_ok_and(|age| age > ttl)
+}
+
+fn read_entry(path: &Path) -> Result<Option<Entry>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let entry = serde_json::from_str(&text)
+        .with_context(|| format!("Corrupt cache entry {}", path.display()))?;
+    Ok(Some(entry))
+}
+
+/// Write through a temporary file, so readers never see half an entry
+fn write_entry(path: &Path, entry: &Entry) -> Result<()> {
+    let temp = path.with_extension(format!("tmp.{}", std::process::id()));
+    fs::write(&temp, serde_json::to_string(entry)?)
+        .and_then(|_| fs::rename(&temp, path))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn remove(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCAL: &str = "http://localhost:8000";
+
+    #[test]
+    fn test_key_covers_model_params_and_messages() {
+        let params = Params::default();
+        let messages = vec![Message::system("Be brief."), Message::user("ping")];
+        let key = |model, params, messages| key(LOCAL, "dev", model, params, messages);
+        let base = key("vortex-v3", &params, &messages);
+        assert_eq!(base.len(), 64);
+        assert_eq!(base, key("vortex-v3", &params, &messages));
+
+        assert_ne!(base, key("vortex-v2", &params, &messages));
+        let seeded = Params {
+            seed: Some(42),
+            ..Params::default()
+        };
+        assert_ne!(base, key("vortex-v3", &seeded, &messages));
+        let other_system = vec![Message::system("Be thorough."), Message::user("ping")];
+        assert_ne!(base, key("vortex-v3", &params, &other_system));
+    }
+
+    #[test]
+    fn test_key_covers_endpoint_and_context() {
+        let params = Params::default();
+        let messages = vec![Message::user("ping")];
+        let base = key(LOCAL, "dev", "vortex-v3", &params, &messages);
+        let staging = "https://vortex.staging.titan.io";
+        assert_ne!(base, key(staging, "dev", "vortex-v3", &params, &messages));
+        assert_ne!(base, key(LOCAL, "prod", "vortex-v3", &params, &messages));
+    }
+
+    #[test]
+    fn test_expiry() {
+        let entry = |age_secs| Entry {
+            created: Utc::now() - chrono::Duration::seconds(age_secs),
+            hits: 0,
+            id: None,
+            model: "vortex-v3".to_string(),
+            text: "pong".to_string(),
+            finish_reason: None,
+            usage: None,
+        };
+        let ttl = Duration::from_secs(3600);
+        assert!(!is_expired(&entry(60), ttl));
+        assert!(is_expired(&entry(7200), ttl));
+    }
+}
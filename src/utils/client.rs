@@ -6,8 +6,25 @@
 //! - Error handling
 
 use anyhow::Result;
-use reqwest::Client;
-use std::time::Duration;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Method, Response, StatusCode};
+use std::time::{Duration, Instant};
+
+/// Outcome of a timed health probe
+pub struct HealthProbe {
+    /// HTTP status returned by the endpoint
+    pub status: StatusCode,
+    /// Time until the response headers arrived
+    pub latency: Duration,
+}
+
+/// A response captured together with its latency
+pub struct TimedResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+    pub latency: Duration,
+}
 
 /// Titan Protocol HTTP Client
 pub struct TitanClient {
@@ -26,19 +43,66 @@ impl TitanClient {
         Ok(Self { client })
     }
 
-    /// Check the health of an endpoint
-    pub async fn check_health(&self, url: &str) -> Result<String, reqwest::Error> {
+    /// Probe an endpoint, recording its status code and latency
+    pub async fn probe(&self, url: &str) -> Result<HealthProbe, reqwest::Error> {
+        let start = Instant::now();
         let response = self.client.get(url).send().await?;
 
-        if response.status().is_success() {
-            Ok("healthy".to_string())
-        } else {
-            Ok(format!("unhealthy: {}", response.status()))
+        Ok(HealthProbe {
+            status: response.status(),
+            latency: start.elapsed(),
+        })
+    }
+
+    /// Send an arbitrary request and time it until the full body arrives
+    pub async fn request(
+        &self,
+        method: Method,
+        url: &str,
+        headers: &[(String, String)],
+        body: Option<&serde_json::Value>,
+        timeout: Duration,
+    ) -> Result<TimedResponse> {
+        let mut request = self.client.request(method, url).timeout(timeout);
+        for (name, value) in headers {
+            request = request.header(name.as_str(), value.as_str());
         }
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+
+        let start = Instant::now();
+        let response = request.send().await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
+
+        Ok(TimedResponse {
+            status,
+            headers,
+            body,
+            latency: start.elapsed(),
+        })
+    }
+
+    /// Send a JSON POST and return as soon as the response headers arrive,
+    /// leaving the body to be read incrementally
+    pub async fn post_stream(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<Response, reqwest::Error> {
+        self.client
+            .post(url)
+            .header("Accept", "text/event-stream, application/x-ndjson")
+            .json(body)
+            .timeout(timeout)
+            .send()
+            .await
     }
 
     /// Send a GET request and return the response body
-    #[allow(dead_code)]
     pub async fn get(&self, url: &str) -> Result<String> {
         let response = self.client.get(url).send().await?;
         let body = response.text().await?;
@@ -46,9 +110,14 @@ impl TitanClient {
     }
 
     /// Send a POST request with JSON body
-    #[allow(dead_code)]
     pub async fn post<T: serde::Serialize>(&self, url: &str, body: &T) -> Result<String> {
-        let response = self.client.post(url).json(body).send().await?;
+        let response = self
+            .client
+            .post(url)
+            .json(body)
+            .send()
+            .await?
+            .error_for_status()?;
         let text = response.text().await?;
         Ok(text)
     }
@@ -0,0 +1,383 @@
+//! Configuration file support for TITAN-CLI
+//!
+//! Settings are read from `~/.titan/config.toml`. Every section is
+//! optional and falls back to built-in defaults when omitted.
+
+use super::duration;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Top-level configuration file layout
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub api: ApiConfig,
+    pub status: StatusConfig,
+    pub vortex: VortexConfig,
+    pub ask: AskConfig,
+    pub usage: UsageConfig,
+    pub cache: CacheConfig,
+}
+
+/// Settings describing the Titan deployment being talked to
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    /// Name of the environment, used to label exported metrics
+    pub context: String,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            context: "local".to_string(),
+        }
+    }
+}
+
+/// Settings for talking to the VORTEX AI engine
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct VortexConfig {
+    /// Base URL of the inference API
+    pub url: String,
+    /// How long to wait for a completion
+    #[serde(deserialize_with = "duration::deserialize")]
+    pub timeout: Duration,
+    /// How long the cached model list is trusted before refetching
+    #[serde(deserialize_with = "duration::deserialize")]
+    pub models_ttl: Duration,
+    /// Short names for models, e.g. `fast = "vortex-lite"`
+    pub aliases: BTreeMap<String, String>,
+}
+
+impl Default for VortexConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://localhost:8000".to_string(),
+            timeout: Duration::from_secs(120),
+            models_ttl: Duration::from_secs(3600),
+            aliases: BTreeMap::new(),
+        }
+    }
+}
+
+/// Settings for the `ask` command
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct AskConfig {
+    /// Model ID or alias used when no `--model` is given
+    pub model: Option<String>,
+    /// Largest stdin or `--file` attachment sent as-is, in KB
+    pub max_attachment_kb: usize,
+    /// Limit on all attachments of one query together, in KB
+    pub max_context_kb: usize,
+    /// How far back `--with-deploys` looks
+    #[serde(deserialize_with = "duration::deserialize")]
+    pub deploy_window: Duration,
+    pub tools: ToolsConfig,
+    /// System prompt for queries and new sessions without one
+    pub system: Option<String>,
+    /// File holding the default system prompt, used when `system` is unset
+    pub system_file: Option<PathBuf>,
+    /// Defaults for the matching `titan ask` flags
+    pub temperature: Option<f64>,
+    pub top_p: Option<f64>,
+    pub max_tokens: Option<u64>,
+    pub stop: Vec<String>,
+    pub seed: Option<u64>,
+}
+
+impl Default for AskConfig {
+    fn default() -> Self {
+        Self {
+            model: None,
+            max_attachment_kb: 256,
+            max_context_kb: 512,
+            deploy_window: Duration::from_secs(24 * 3600),
+            tools: ToolsConfig::default(),
+            system: None,
+            system_file: None,
+            temperature: None,
+            top_p: None,
+            max_tokens: None,
+            stop: Vec::new(),
+            seed: None,
+        }
+    }
+}
+
+/// Tools VORTEX may call while answering (`[ask.tools]`)
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ToolsConfig {
+    /// Offer tools without passing `--tools`
+    pub enabled: bool,
+    /// Tools that run without asking first
+    pub allow: Vec<String>,
+    /// Tool rounds allowed before VORTEX has to answer
+    pub max_rounds: usize,
+}
+
+impl Default for ToolsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allow: Vec::new(),
+            max_rounds: 5,
+        }
+    }
+}
+
+/// Token prices and spending limits for VORTEX queries
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct UsageConfig {
+    /// Label printed after costs
+    pub currency: String,
+    /// Price per million tokens, keyed by model ID
+    pub prices: BTreeMap<String, Price>,
+    /// Most that may be spent per calendar day
+    pub daily_budget: Option<f64>,
+    /// Most that may be spent within one `--session`
+    pub session_budget: Option<f64>,
+    /// What `ask` does once a budget is used up
+    pub on_exceeded: BudgetAction,
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self {
+            currency: "USD".to_string(),
+            prices: BTreeMap::new(),
+            daily_budget: None,
+            session_budget: None,
+            on_exceeded: BudgetAction::Warn,
+        }
+    }
+}
+
+/// Local cache of VORTEX responses (`[cache]`)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Answer repeated identical queries from disk
+    pub enabled: bool,
+    /// How long a cached answer is served
+    #[serde(deserialize_with = "duration::deserialize")]
+    pub ttl: Duration,
+    /// Size the cache is pruned back to, in MB
+    pub max_size_mb: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl: Duration::from_secs(24 * 3600),
+            max_size_mb: 50,
+        }
+    }
+}
+
+/// Price of a model per million tokens
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Price {
+    /// Prompt tokens
+    pub input: f64,
+    /// Completion tokens
+    pub output: f64,
+}
+
+/// Reaction to an exceeded budget
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetAction {
+    /// Print a warning and send the query anyway
+    Warn,
+    /// Refuse to send the query
+    Refuse,
+}
+
+/// Settings for the `status` command
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct StatusConfig {
+    /// Warn when a TLS certificate expires within this many days
+    pub cert_warn_days: i64,
+    /// Append every status run to the local history file
+    pub record_history: bool,
+    /// Actions fired on state transitions while watching
+    pub alerts: AlertConfig,
+    /// Extra version compatibility rules, checked alongside the built-in ones
+    pub compat: Vec<CompatRule>,
+    /// End-to-end checks run by `titan status --synthetic`
+    pub synthetic: Vec<SyntheticCheck>,
+}
+
+impl Default for StatusConfig {
+    fn default() -> Self {
+        Self {
+            cert_warn_days: 14,
+            record_history: true,
+            alerts: AlertConfig::default(),
+            compat: Vec::new(),
+            synthetic: Vec::new(),
+        }
+    }
+}
+
+/// A version compatibility rule
+///
+/// When `service` runs a version matching `version`, every service in
+/// `requires` must run a version matching the given requirement.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompatRule {
+    pub service: String,
+    pub version: String,
+    pub requires: BTreeMap<String, String>,
+}
+
+/// A user-defined end-to-end check made of sequential HTTP steps
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyntheticCheck {
+    pub name: String,
+    pub steps: Vec<SyntheticStep>,
+}
+
+/// One HTTP request within a synthetic check
+///
+/// The target is either a full `url` or a registered `service` plus `path`.
+/// Strings may reference earlier captures as `{{name}}` and environment
+/// variables as `{{env.NAME}}`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyntheticStep {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default = "default_method")]
+    pub method: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub service: Option<String>,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// JSON request body
+    #[serde(default)]
+    pub body: Option<serde_json::Value>,
+    #[serde(
+        default = "default_step_timeout",
+        deserialize_with = "duration::deserialize"
+    )]
+    pub timeout: Duration,
+    #[serde(default)]
+    pub expect: StepExpect,
+    /// Variables to capture from the JSON response, as name → path
+    #[serde(default)]
+    pub capture: BTreeMap<String, String>,
+}
+
+/// Assertions on a synthetic step's response
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct StepExpect {
+    /// Expected HTTP status; any 2xx when omitted
+    pub status: Option<u16>,
+    pub max_latency_ms: Option<u64>,
+    /// JSON paths that must be present
+    pub exists: Vec<String>,
+    /// JSON paths that must hold a non-empty string, array or object
+    pub non_empty: Vec<String>,
+    /// JSON paths that must equal the given value
+    pub equals: BTreeMap<String, serde_json::Value>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+fn default_step_timeout() -> Duration {
+    Duration::from_secs(30)
+}
+
+/// Transition alerting for `titan status --watch`
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct AlertConfig {
+    /// How long a new state must hold before actions fire
+    #[serde(deserialize_with = "duration::deserialize")]
+    pub debounce: Duration,
+    /// Transitions within `flap_window` that mark a service as flapping
+    pub flap_threshold: usize,
+    /// Window used to count transitions for flap detection
+    #[serde(deserialize_with = "duration::deserialize")]
+    pub flap_window: Duration,
+    /// Actions to run on matching transitions
+    pub hooks: Vec<HookConfig>,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            debounce: Duration::from_secs(30),
+            flap_threshold: 4,
+            flap_window: Duration::from_secs(600),
+            hooks: Vec::new(),
+        }
+    }
+}
+
+/// A single transition hook
+///
+/// `on` takes patterns like `healthy->down`, `*->healthy` or just
+/// `degraded` (any transition into that state).
+#[derive(Debug, Clone, Deserialize)]
+pub struct HookConfig {
+    pub on: Vec<String>,
+    /// Restrict the hook to one service
+    #[serde(default)]
+    pub service: Option<String>,
+    /// Shell command to run, with the event in `TITAN_*` environment variables
+    #[serde(default)]
+    pub command: Option<String>,
+    /// URL that receives the event as a JSON POST
+    #[serde(default)]
+    pub webhook: Option<String>,
+}
+
+impl Config {
+    /// Load the configuration file, using defaults if it does not exist
+    pub fn load() -> Result<Self> {
+        let path = config_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&text)
+            .with_context(|| format!("Invalid configuration in {}", path.display()))
+    }
+
+    /// Name of the active context, preferring `TITAN_CONTEXT` over the file
+    pub fn context(&self) -> String {
+        std::env::var("TITAN_CONTEXT").unwrap_or_else(|_| self.api.context.clone())
+    }
+}
+
+/// Directory holding all TITAN-CLI state (`~/.titan`)
+pub fn titan_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".titan")
+}
+
+/// Path of the configuration file
+pub fn config_path() -> PathBuf {
+    titan_dir().join("config.toml")
+}
@@ -0,0 +1,86 @@
+//! Human-friendly duration parsing
+//!
+//! Accepts compact spans such as `90s`, `30m`, `24h`, `7d` or `2w` for
+//! flags like `--since`.
+
+use serde::{Deserialize, Deserializer};
+use std::time::Duration;
+
+/// Parse a compact duration string like `24h` or `7d`
+pub fn parse(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (digits, unit) = input.split_at(split);
+
+    let value: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid duration '{}': expected e.g. 30m, 24h, 7d", input))?;
+
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        "w" => 604_800,
+        "" => {
+            return Err(format!(
+                "duration '{}' is missing a unit (s, m, h, d, w)",
+                input
+            ))
+        }
+        other => {
+            return Err(format!(
+                "unknown duration unit '{}' (use s, m, h, d, w)",
+                other
+            ))
+        }
+    };
+
+    Ok(Duration::from_secs(value * seconds))
+}
+
+/// Deserialize a compact duration string from a configuration file
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    parse(&text).map_err(serde::de::Error::custom)
+}
+
+/// Format a duration the way operators read it, e.g. `1h 5m`, `42s` or `7d`
+pub fn humanize(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (major, major_unit, minor, minor_unit) = match secs {
+        0..=59 => return format!("{}s", secs),
+        60..=3_599 => (secs / 60, "m", secs % 60, "s"),
+        3_600..=86_399 => (secs / 3_600, "h", (secs % 3_600) / 60, "m"),
+        _ => (secs / 86_400, "d", (secs % 86_400) / 3_600, "h"),
+    };
+
+    if minor == 0 {
+        format!("{}{}", major, major_unit)
+    } else {
+        format!("{}{} {}{}", major, major_unit, minor, minor_unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_durations() {
+        assert_eq!(parse("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse("24h"), Ok(Duration::from_secs(86_400)));
+        assert_eq!(parse("7d"), Ok(Duration::from_secs(604_800)));
+        assert!(parse("24").is_err());
+        assert!(parse("1y").is_err());
+    }
+
+    #[test]
+    fn test_humanize() {
+        assert_eq!(humanize(Duration::from_secs(42)), "42s");
+        assert_eq!(humanize(Duration::from_secs(3_900)), "1h 5m");
+        assert_eq!(humanize(Duration::from_secs(86_400)), "1d");
+    }
+}
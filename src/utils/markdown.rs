@@ -0,0 +1,475 @@
+//! Terminal Markdown rendering
+//!
+//! Renders the Markdown in VORTEX responses one line at a time, so text
+//! can be fed in while it streams. Tables are held back until their last
+//! row, and fenced code blocks are highlighted line by line.
+
+use colored::Colorize;
+use std::sync::OnceLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::as_24_bit_terminal_escaped;
+use textwrap::core::display_width;
+
+/// Incremental Markdown to ANSI renderer
+pub struct MarkdownRenderer {
+    width: usize,
+    /// Text of the line currently being received
+    pending: String,
+    code: Option<CodeBlock>,
+    table: Vec<String>,
+}
+
+/// State of an open fenced code block
+struct CodeBlock {
+    fence: String,
+    highlighter: Option<HighlightLines<'static>>,
+}
+
+impl MarkdownRenderer {
+    /// Create a renderer that wraps text to `width` columns
+    pub fn new(width: usize) -> Self {
+        Self {
+            width: width.max(20),
+            pending: String::new(),
+            code: None,
+            table: Vec::new(),
+        }
+    }
+
+    /// Feed streamed text, returning the rendering of every completed line
+    pub fn push(&mut self, text: &str) -> String {
+        self.pending.push_str(text);
+
+        let mut out = String::new();
+        while let Some(pos) = self.pending.find('\n') {
+            let line: String = self.pending.drain(..=pos).collect();
+            self.render_line(line.trim_end_matches(['\n', '\r']), &mut out);
+        }
+        out
+    }
+
+    /// Render whatever is still held back at the end of the response
+    pub fn finish(&mut self) -> String {
+        let mut out = String::new();
+        if !self.pending.is_empty() {
+            let line = std::mem::take(&mut self.pending);
+            self.render_line(&line, &mut out);
+        }
+        self.flush_table(&mut out);
+        if self.code.take().is_some() {
+            out.push_str(&format!("{}\n", "╰─".dimmed()));
+        }
+        out
+    }
+
+    fn render_line(&mut self, line: &str, out: &mut String) {
+        if let Some(code) = self.code.as_mut() {
+            if is_fence_close(line, &code.fence) {
+                self.code = None;
+                out.push_str(&format!("{}\n", "╰─".dimmed()));
+            } else {
+                out.push_str(&format!("{} {}\n", "│".dimmed(), highlight(code, line)));
+            }
+            return;
+        }
+
+        let trimmed = line.trim_start();
+        if is_table_row(trimmed) {
+            self.table.push(trimmed.to_string());
+            return;
+        }
+        self.flush_table(out);
+
+        if let Some((fence, lang)) = fence_open(trimmed) {
+            out.push_str(&format!("{} {}\n", "╭─".dimmed(), lang.dimmed()));
+            self.code = Some(CodeBlock {
+                fence,
+                highlighter: highlighter_for(&lang),
+            });
+        } else if trimmed.is_empty() {
+            out.push('\n');
+        } else if let Some((level, text)) = heading(trimmed) {
+            let text = text.trim_end_matches('#').trim();
+            let styled = match level {
+                1 => text.to_uppercase().cyan().bold().underline(),
+                2 => text.cyan().bold(),
+                _ => text.bold(),
+            };
+            out.push_str(&format!("{}\n", styled));
+        } else if is_rule(trimmed) {
+            out.push_str(&format!("{}\n", "─".repeat(self.width).dimmed()));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            let bar = format!("{} ", "│".dimmed());
+            let text = render_inline(quote.trim()).italic().to_string();
+            out.push_str(&wrap(&text, self.width, &bar, &bar));
+        } else if let Some((indent, marker, text)) = list_item(line) {
+            let first = format!("{}{} ", " ".repeat(indent), marker);
+            let rest = " ".repeat(display_width(&first));
+            out.push_str(&wrap(&render_inline(text), self.width, &first, &rest));
+        } else {
+            out.push_str(&wrap(&render_inline(trimmed), self.width, "", ""));
+        }
+    }
+
+    /// Render buffered table rows with aligned columns
+    fn flush_table(&mut self, out: &mut String) {
+        if self.table.is_empty() {
+            return;
+        }
+        let lines = std::mem::take(&mut self.table);
+        let has_header = lines.get(1).is_some_and(|l| is_separator_row(l));
+        let rows: Vec<Vec<String>> = lines
+            .iter()
+            .filter(|l| !is_separator_row(l))
+            .map(|l| split_cells(l).iter().map(|c| render_inline(c)).collect())
+            .collect();
+
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut widths = vec![0; columns];
+        for row in &rows {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(display_width(cell));
+            }
+        }
+
+        // Too wide to align: show the rows as they came
+        let total = widths.iter().sum::<usize>() + 3 * columns.saturating_sub(1);
+        if total > self.width {
+            for line in &lines {
+                out.push_str(&format!("{}\n", line));
+            }
+            return;
+        }
+
+        let separator = format!(" {} ", "│".dimmed());
+        for (r, row) in rows.iter().enumerate() {
+            let cells: Vec<String> = (0..columns)
+                .map(|i| {
+                    let cell = row.get(i).map(String::as_str).unwrap_or("");
+                    let padded = format!("{}{}", cell, " ".repeat(widths[i] - display_width(cell)));
+                    if r == 0 && has_header {
+                        padded.bold().to_string()
+                    } else {
+                        padded
+                    }
+                })
+                .collect();
+            out.push_str(cells.join(&separator).trim_end());
+            out.push('\n');
+
+            if r == 0 && has_header {
+                let rule: Vec<String> = widths.iter().map(|w| "─".repeat(*w)).collect();
+                out.push_str(&format!("{}\n", rule.join("─┼─").dimmed()));
+            }
+        }
+    }
+}
+
+/// Word-wrap styled text, ignoring escape sequences when measuring
+fn wrap(text: &str, width: usize, first: &str, rest: &str) -> String {
+    let options = textwrap::Options::new(width)
+        .initial_indent(first)
+        .subsequent_indent(rest)
+        .wrap_algorithm(textwrap::WrapAlgorithm::FirstFit);
+    let mut out = textwrap::wrap(text, options).join("\n");
+    out.push('\n');
+    out
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+        Some((level, line[level..].trim()))
+    } else {
+        None
+    }
+}
+
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|&m| compact.chars().all(|c| c == m))
+}
+
+/// Match `- item`, `* item`, `1. item` and task items, returning indent, marker and text
+fn list_item(line: &str) -> Option<(usize, String, &str)> {
+    let trimmed = line.trim_start();
+    let indent = (line.len() - trimmed.len()) / 2 * 2;
+
+    let (marker, text) = if let Some(text) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|m| trimmed.strip_prefix(m))
+    {
+        ("•".to_string(), text)
+    } else {
+        let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+        let rest = &trimmed[digits..];
+        if digits == 0 || !(rest.starts_with(". ") || rest.starts_with(") ")) {
+            return None;
+        }
+        (format!("{}.", &trimmed[..digits]), &rest[2..])
+    };
+
+    let (marker, text) = if let Some(task) = text.strip_prefix("[ ] ") {
+        ("☐".to_string(), task)
+    } else if let Some(task) = text
+        .strip_prefix("[x] ")
+        .or_else(|| text.strip_prefix("[X] "))
+    {
+        ("☑".to_string(), task)
+    } else {
+        (marker, text)
+    };
+
+    Some((indent, marker.cyan().to_string(), text))
+}
+
+/// Opening fence and its language, for lines like "```rust"
+fn fence_open(line: &str) -> Option<(String, String)> {
+    let marker = line.chars().next().filter(|&c| c == '`' || c == '~')?;
+    let len = line.chars().take_while(|&c| c == marker).count();
+    if len < 3 {
+        return None;
+    }
+    let lang = line[len..].split_whitespace().next().unwrap_or("");
+    Some((line[..len].to_string(), lang.to_string()))
+}
+
+fn is_fence_close(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    let marker = fence.chars().next().unwrap_or('`');
+    trimmed.len() >= fence.len() && trimmed.chars().all(|c| c == marker)
+}
+
+fn is_table_row(line: &str) -> bool {
+    line.starts_with('|') && line.matches('|').count() >= 2
+}
+
+fn is_separator_row(line: &str) -> bool {
+    let cells = split_cells(line);
+    !cells.is_empty()
+        && cells.iter().all(|c| {
+            let c = c.trim_matches(':');
+            !c.is_empty() && c.chars().all(|ch| ch == '-')
+        })
+}
+
+fn split_cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.replace("\\|", "\u{0}")
+        .split('|')
+        .map(|cell| cell.trim().replace('\u{0}', "|"))
+        .collect()
+}
+
+/// Apply bold, italic, strikethrough, code span and link styling
+pub fn render_inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let boundary = i == 0 || !chars[i - 1].is_alphanumeric();
+
+        if c == '`' {
+            if let Some(end) = find(&chars, i + 1, "`") {
+                let code: String = chars[i + 1..end].iter().collect();
+                out.push_str(&code.yellow().to_string());
+                i = end + 1;
+                continue;
+            }
+        }
+
+        let double = [
+            ("**", Style::Bold),
+            ("__", Style::Bold),
+            ("~~", Style::Strike),
+        ]
+        .into_iter()
+        .filter(|(delim, _)| *delim != "__" || boundary)
+        .find_map(|(delim, style)| {
+            if !starts_with(&chars, i, delim) {
+                return None;
+            }
+            find(&chars, i + 2, delim)
+                .filter(|&end| end > i + 2)
+                .map(|end| (end, style))
+        });
+        if let Some((end, style)) = double {
+            let inner: String = chars[i + 2..end].iter().collect();
+            out.push_str(&style.apply(&inner));
+            i = end + 2;
+            continue;
+        }
+
+        if (c == '*' || (c == '_' && boundary))
+            && chars
+                .get(i + 1)
+                .is_some_and(|n| !n.is_whitespace() && *n != c)
+        {
+            if let Some(end) = find(&chars, i + 1, &c.to_string()) {
+                let inner: String = chars[i + 1..end].iter().collect();
+                out.push_str(&Style::Italic.apply(&inner));
+                i = end + 1;
+                continue;
+            }
+        }
+
+        if c == '[' {
+            if let Some(mid) = find(&chars, i + 1, "](") {
+                if let Some(end) = find(&chars, mid + 2, ")") {
+                    let label: String = chars[i + 1..mid].iter().collect();
+                    let url: String = chars[mid + 2..end].iter().collect();
+                    out.push_str(&format!(
+                        "{} {}",
+                        label.underline(),
+                        format!("({})", url).dimmed()
+                    ));
+                    i = end + 1;
+                    continue;
+                }
+            }
+        }
+
+        out.push(c);
+        i += 1;
+    }
+
+    out
+}
+
+#[derive(Clone, Copy)]
+enum Style {
+    Bold,
+    Italic,
+    Strike,
+}
+
+impl Style {
+    fn apply(self, text: &str) -> String {
+        match self {
+            Style::Bold => text.bold().to_string(),
+            Style::Italic => text.italic().to_string(),
+            Style::Strike => text.strikethrough().to_string(),
+        }
+    }
+}
+
+fn starts_with(chars: &[char], at: usize, pattern: &str) -> bool {
+    pattern
+        .chars()
+        .enumerate()
+        .all(|(k, p)| chars.get(at + k) == Some(&p))
+}
+
+/// Index of the next occurrence of `pattern` at or after `from`
+fn find(chars: &[char], from: usize, pattern: &str) -> Option<usize> {
+    (from..chars.len()).find(|&i| starts_with(chars, i, pattern))
+}
+
+fn syntaxes() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme() -> &'static Theme {
+    static THEME: OnceLock<Theme> = OnceLock::new();
+    THEME.get_or_init(|| {
+        let mut themes = ThemeSet::load_defaults().themes;
+        themes
+            .remove("base16-ocean.dark")
+            .unwrap_or_else(|| themes.into_values().next().unwrap_or_default())
+    })
+}
+
+/// Highlighter for a fenced block's language, if it is known and color is on
+fn highlighter_for(lang: &str) -> Option<HighlightLines<'static>> {
+    if lang.is_empty() || !colored::control::SHOULD_COLORIZE.should_colorize() {
+        return None;
+    }
+    let syntax = syntaxes().find_syntax_by_token(lang)?;
+    Some(HighlightLines::new(syntax, theme()))
+}
+
+fn highlight(code: &mut CodeBlock, line: &str) -> String {
+    let Some(highlighter) = code.highlighter.as_mut() else {
+        return line.to_string();
+    };
+    let with_newline = format!("{}\n", line);
+    match highlighter.highlight_line(&with_newline, syntaxes()) {
+        Ok(ranges) => format!(
+            "{}\x1b[0m",
+            as_24_bit_terminal_escaped(&ranges, false).trim_end_matches('\n')
+        ),
+        Err(_) => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Drop ANSI escape sequences, so assertions hold with or without color
+    fn plain(text: String) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                // CSI sequences end with a letter, e.g. `\x1b[1;36m`
+                chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_renders_completed_lines_only() {
+        let mut renderer = MarkdownRenderer::new(80);
+        assert_eq!(plain(renderer.push("## Sta")), "");
+        assert_eq!(plain(renderer.push("tus\nAll **good** so")), "Status\n");
+        assert_eq!(plain(renderer.finish()), "All good so\n");
+    }
+
+    #[test]
+    fn test_lists_code_and_wrapping() {
+        let mut renderer = MarkdownRenderer::new(20);
+        let out = plain(renderer.push(
+            "- first item that is long enough to wrap\n  1. nested\n```sh\ntitan status\n```\n",
+        ));
+        assert_eq!(
+            out,
+            "• first item that is\n  long enough to\n  wrap\n  1. nested\n╭─ sh\n│ titan status\n╰─\n"
+        );
+    }
+
+    #[test]
+    fn test_table_columns_are_aligned() {
+        let mut renderer = MarkdownRenderer::new(80);
+        let mut out = renderer.push("| Service | State |\n|---|:---:|\n| VORTEX | `down` |\n");
+        out.push_str(&renderer.push("\n"));
+        assert_eq!(
+            plain(out),
+            "Service │ State\n────────┼──────\nVORTEX  │ down\n\n"
+        );
+    }
+
+    #[test]
+    fn test_inline_markers() {
+        assert_eq!(
+            plain(render_inline(
+                "a *b* __c__ ~~d~~ [e](http://x) snake_case_name"
+            )),
+            "a b c d e (http://x) snake_case_name"
+        );
+        assert_eq!(plain(render_inline("2 * 3 * 4")), "2 * 3 * 4");
+    }
+}
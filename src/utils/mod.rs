@@ -2,7 +2,31 @@
 //!
 //! Provides shared functionality:
 //! - HTTP client wrapper
+//! - Local VORTEX response cache
+//! - Configuration file loading
+//! - Duration parsing
+//! - Record of the last failed command
+//! - Terminal Markdown rendering
+//! - VORTEX model catalog and aliases
+//! - Service registry and name resolution
+//! - Rotation of the JSONL history files
+//! - Stored VORTEX conversations
+//! - Prompt templates
 //! - UI components and styling
+//! - VORTEX usage ledger and budgets
+//! - VORTEX inference API client
 
+pub mod cache;
 pub mod client;
+pub mod config;
+pub mod duration;
+pub mod last_error;
+pub mod markdown;
+pub mod models;
+pub mod registry;
+pub mod rotate;
+pub mod session;
+pub mod templates;
 pub mod ui;
+pub mod usage;
+pub mod vortex;
@@ -0,0 +1,163 @@
+//! VORTEX model catalog
+//!
+//! The model list from `GET /v1/models` is cached in
+//! `~/.titan/cache/models.json` for `[vortex] models_ttl`, so checking
+//! `--model` does not cost a request on every query. Aliases from
+//! `[vortex.aliases]` map short names to model IDs.
+
+use super::config::{self, VortexConfig};
+use super::vortex::{ModelInfo, VortexClient};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Models served by one VORTEX endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Catalog {
+    /// Endpoint the list was fetched from
+    pub url: String,
+    pub fetched: DateTime<Utc>,
+    pub models: Vec<ModelInfo>,
+    /// Set when a refresh failed and an expired cache was used instead
+    #[serde(skip)]
+    pub stale: bool,
+}
+
+impl Catalog {
+    /// Time since the list was fetched
+    pub fn age(&self) -> Duration {
+        (Utc::now() - self.fetched).to_std().unwrap_or_default()
+    }
+
+    pub fn find(&self, id: &str) -> Option<&ModelInfo> {
+        self.models.iter().find(|m| m.id == id)
+    }
+}
+
+fn cache_path() -> PathBuf {
+    config::titan_dir().join("cache").join("models.json")
+}
+
+/// Load the catalog, fetching it when the cache is missing, expired or
+/// `refresh` is set
+///
+/// If VORTEX cannot be reached, an expired cache for the same endpoint is
+/// returned with `stale` set rather than failing.
+pub async fn catalog(
+    vortex: &VortexClient,
+    config: &VortexConfig,
+    refresh: bool,
+) -> Result<Catalog> {
+    let cached = read_cache().filter(|c| c.url == vortex.base_url());
+    match cached {
+        Some(cached) if !refresh && cached.age() < config.models_ttl => return Ok(cached),
+        _ => {}
+    }
+
+    match vortex.models().await {
+        Ok(models) => {
+            let catalog = Catalog {
+                url: vortex.base_url().to_string(),
+                fetched: Utc::now(),
+                models,
+                stale: false,
+            };
+            // A cache that cannot be written only costs a refetch next time
+            let _ = write_cache(&catalog);
+            Ok(catalog)
+        }
+        Err(e) => match cached {
+            Some(mut cached) => {
+                cached.stale = true;
+                Ok(cached)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+fn read_cache() -> Option<Catalog> {
+    let text = std::fs::read_to_string(cache_path()).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+fn write_cache(catalog: &Catalog) -> Result<()> {
+    let path = cache_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(catalog)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Map an alias to its model ID; other names pass through unchanged
+pub fn resolve_alias(name: &str, aliases: &BTreeMap<String, String>) -> String {
+    aliases
+        .get(name)
+        .cloned()
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Look a model up in the catalog, suggesting close IDs when it is unknown
+pub fn check<'a>(id: &str, catalog: &'a Catalog) -> Result<&'a ModelInfo> {
+    if let Some(model) = catalog.find(id) {
+        return Ok(model);
+    }
+
+    let max_distance = (id.chars().count() / 3).max(1);
+    let mut close: Vec<(usize, &str)> = catalog
+        .models
+        .iter()
+        .map(|m| (strsim::damerau_levenshtein(id, &m.id), m.id.as_str()))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    close.sort_by_key(|(distance, _)| *distance);
+
+    match close.first() {
+        Some((_, suggestion)) => bail!(
+            "Unknown model '{}'. Did you mean {}? (see `titan models`)",
+            id,
+            suggestion
+        ),
+        None => {
+            let available: Vec<&str> = catalog.models.iter().map(|m| m.id.as_str()).collect();
+            bail!(
+                "Unknown model '{}'. Available models: {}",
+                id,
+                available.join(", ")
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(id: &str) -> ModelInfo {
+        serde_json::from_value(serde_json::json!({ "id": id })).unwrap()
+    }
+
+    #[test]
+    fn test_aliases_and_unknown_models() {
+        let aliases = BTreeMap::from([("fast".to_string(), "vortex-lite".to_string())]);
+        assert_eq!(resolve_alias("fast", &aliases), "vortex-lite");
+        assert_eq!(resolve_alias("vortex-v3", &aliases), "vortex-v3");
+
+        let catalog = Catalog {
+            url: "http://localhost:8000".to_string(),
+            fetched: Utc::now(),
+            models: vec![model("vortex-v3"), model("vortex-lite")],
+            stale: false,
+        };
+        assert!(check("vortex-v3", &catalog).is_ok());
+        let typo = check("vortex-v4", &catalog).unwrap_err().to_string();
+        assert!(typo.contains("Did you mean vortex-v3?"), "{}", typo);
+        let unknown = check("gpt", &catalog).unwrap_err().to_string();
+        assert!(unknown.contains("vortex-v3, vortex-lite"), "{}", unknown);
+    }
+}
@@ -0,0 +1,166 @@
+//! Service registry for the Titan Protocol
+//!
+//! Single source of truth for the services the CLI knows about, and
+//! resolution of user-supplied service names against it.
+
+use anyhow::{bail, Result};
+
+/// Titan Protocol version spoken by this CLI
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Service definition with name, endpoint, and icon
+pub struct Service {
+    pub name: &'static str,
+    pub icon: &'static str,
+    pub endpoint: &'static str,
+    pub description: &'static str,
+    /// Services this one needs in order to be healthy
+    pub depends_on: &'static [&'static str],
+}
+
+/// List of all Titan Protocol services to check
+pub const SERVICES: &[Service] = &[
+    Service {
+        name: "CERBERUS",
+        icon: "🛡️",
+        endpoint: "http://localhost:8080/health",
+        description: "API Gateway (L2)",
+        depends_on: &["VORTEX", "OPTICUS"],
+    },
+    Service {
+        name: "KRONOS",
+        icon: "⏰",
+        endpoint: "http://localhost:3000/health",
+        description: "Task Scheduler",
+        depends_on: &[],
+    },
+    Service {
+        name: "HERMES",
+        icon: "📨",
+        endpoint: "http://localhost:50051",
+        description: "Event Bus (gRPC)",
+        depends_on: &[],
+    },
+    Service {
+        name: "VORTEX",
+        icon: "🧠",
+        endpoint: "http://localhost:8000/health",
+        description: "AI Engine",
+        depends_on: &["HERMES", "KRONOS"],
+    },
+    Service {
+        name: "OPTICUS",
+        icon: "👁️",
+        endpoint: "http://localhost:8100/health",
+        description: "Vision Pipeline",
+        depends_on: &["HERMES", "KRONOS"],
+    },
+];
+
+/// Look up a service by its exact (case-insensitive) name
+pub fn find(name: &str) -> Option<&'static Service> {
+    SERVICES.iter().find(|s| s.name.eq_ignore_ascii_case(name))
+}
+
+/// Resolve a user-supplied name to a registered service
+///
+/// Exact names win, then unique prefixes (`vor` → VORTEX). Anything else is
+/// an error that lists the candidates or suggests close spellings.
+pub fn resolve(input: &str) -> Result<&'static Service> {
+    let wanted = input.trim().to_lowercase();
+    if wanted.is_empty() {
+        bail!("Service name cannot be empty");
+    }
+
+    if let Some(service) = find(&wanted) {
+        return Ok(service);
+    }
+
+    let prefixed: Vec<&'static Service> = SERVICES
+        .iter()
+        .filter(|s| s.name.to_lowercase().starts_with(&wanted))
+        .collect();
+    match prefixed.as_slice() {
+        [service] => return Ok(service),
+        [] => {}
+        many => bail!(
+            "Service name '{}' is ambiguous: could be {}",
+            input,
+            join_names(many)
+        ),
+    }
+
+    let suggestions = suggest(&wanted);
+    if suggestions.is_empty() {
+        let available: Vec<&str> = SERVICES.iter().map(|s| s.name).collect();
+        bail!(
+            "Unknown service '{}'. Available services: {}",
+            input,
+            available.join(", ")
+        );
+    }
+    bail!(
+        "Unknown service '{}'. Did you mean {}?",
+        input,
+        join_names(&suggestions)
+    )
+}
+
+/// Registered services within a small edit distance of `input`
+fn suggest(input: &str) -> Vec<&'static Service> {
+    let max_distance = (input.chars().count() / 3).max(1);
+    let mut scored: Vec<(usize, &'static Service)> = SERVICES
+        .iter()
+        .map(|s| {
+            let name = s.name.to_lowercase();
+            // Compare against the name truncated to the input length too, so
+            // misspelled prefixes such as `kron0` still find KRONOS
+            let prefix: String = name.chars().take(input.chars().count()).collect();
+            let distance = strsim::damerau_levenshtein(input, &name)
+                .min(strsim::damerau_levenshtein(input, &prefix));
+            (distance, s)
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+
+    scored.sort_by_key(|(distance, _)| *distance);
+    scored.into_iter().map(|(_, s)| s).collect()
+}
+
+/// Format service names as `A`, `A or B` or `A, B or C`
+fn join_names(services: &[&'static Service]) -> String {
+    let names: Vec<&str> = services.iter().map(|s| s.name).collect();
+    match names.as_slice() {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [rest @ .., last] => format!("{} or {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved(input: &str) -> Result<&'static str> {
+        resolve(input).map(|s| s.name)
+    }
+
+    #[test]
+    fn test_exact_and_prefix_names_resolve() {
+        assert_eq!(resolved("vortex").unwrap(), "VORTEX");
+        assert_eq!(resolved("HERMES").unwrap(), "HERMES");
+        assert_eq!(resolved("opt").unwrap(), "OPTICUS");
+    }
+
+    #[test]
+    fn test_typos_suggest_close_names() {
+        let err = resolved("vortx").unwrap_err().to_string();
+        assert!(err.contains("Did you mean VORTEX?"), "{}", err);
+    }
+
+    #[test]
+    fn test_unknown_names_list_services() {
+        let err = resolved("zeus").unwrap_err().to_string();
+        assert!(err.contains("Available services"), "{}", err);
+    }
+}
@@ -0,0 +1,75 @@
+//! Size-based rotation of the JSONL history files
+//!
+//! Once an active file such as `status-history.jsonl` grows past its limit,
+//! it becomes `status-history.1.jsonl`, older generations shift up by one
+//! and the oldest is dropped.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Path of the n-th rotated generation of `path`
+pub fn rotated_path(path: &Path, generation: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{}.{}.{}", stem, generation, ext.to_string_lossy()),
+        None => format!("{}.{}", stem, generation),
+    };
+    path.with_file_name(name)
+}
+
+/// The rotated generations followed by the active file, oldest first
+pub fn files(path: &Path, generations: usize) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = (1..=generations)
+        .rev()
+        .map(|generation| rotated_path(path, generation))
+        .collect();
+    files.push(path.to_path_buf());
+    files
+}
+
+/// Shift rotated files up by one generation when the active file is full
+pub fn rotate_if_needed(path: &Path, max_bytes: u64, generations: usize) -> Result<()> {
+    let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    if size < max_bytes {
+        return Ok(());
+    }
+
+    let _ = fs::remove_file(rotated_path(path, generations));
+    for generation in (1..generations).rev() {
+        let from = rotated_path(path, generation);
+        if from.exists() {
+            fs::rename(&from, rotated_path(path, generation + 1))
+                .with_context(|| format!("Failed to rotate {}", from.display()))?;
+        }
+    }
+    fs::rename(path, rotated_path(path, 1))
+        .with_context(|| format!("Failed to rotate {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_shifts_generations() {
+        let dir = std::env::temp_dir().join(format!("titan-rotate-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("deploy-history.jsonl");
+        assert_eq!(rotated_path(&path, 2), dir.join("deploy-history.2.jsonl"));
+
+        for round in 1..=4 {
+            fs::write(&path, format!("round {}\n", round)).unwrap();
+            rotate_if_needed(&path, 1, 2).unwrap();
+        }
+        fs::write(&path, "small").unwrap();
+        rotate_if_needed(&path, 1024, 2).unwrap();
+
+        let contents: Vec<String> = files(&path, 2)
+            .iter()
+            .map(|file| fs::read_to_string(file).unwrap())
+            .collect();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(contents, ["round 3\n", "round 4\n", "small"]);
+    }
+}
@@ -0,0 +1,222 @@
+//! Persistent VORTEX conversations
+//!
+//! Each session is a JSON file in `~/.titan/sessions/` named after its ID.
+//! `titan ask --session <id>` appends to it, and `titan sessions` lists,
+//! shows, exports and deletes them.
+
+use super::config;
+use super::vortex::Message;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// A stored conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub id: String,
+    /// Model used for the most recent turn
+    pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+    #[serde(default)]
+    pub turns: Vec<Turn>,
+}
+
+/// One message of a stored conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Turn {
+    pub role: String,
+    pub content: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Session {
+    pub fn new(id: &str, model: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            id: id.to_string(),
+            model: model.to_string(),
+            system: None,
+            created: now,
+            updated: now,
+            turns: Vec::new(),
+        }
+    }
+
+    /// Messages to send to VORTEX: the system prompt followed by every turn
+    pub fn messages(&self) -> Vec<Message> {
+        self.system
+            .iter()
+            .map(Message::system)
+            .chain(
+                self.turns
+                    .iter()
+                    .map(|turn| Message::new(&turn.role, turn.content.as_str())),
+            )
+            .collect()
+    }
+
+    /// Append a message, stamping it with the current time
+    pub fn push(&mut self, message: Message) {
+        let now = Utc::now();
+        self.turns.push(Turn {
+            role: message.role,
+            content: message.content,
+            timestamp: now,
+        });
+        self.updated = now;
+    }
+
+    /// Render the conversation as a Markdown document
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Session {}\n\n", self.id);
+        out.push_str(&format!("- Model: {}\n", self.model));
+        out.push_str(&format!("- Started: {}\n", format_time(&self.created)));
+        out.push_str(&format!("- Updated: {}\n", format_time(&self.updated)));
+        if let Some(ref system) = self.system {
+            out.push_str(&format!("\n## System\n\n{}\n", system));
+        }
+        for turn in &self.turns {
+            let speaker = match turn.role.as_str() {
+                "user" => "User",
+                "assistant" => "VORTEX",
+                other => other,
+            };
+            out.push_str(&format!(
+                "\n## {} · {}\n\n{}\n",
+                speaker,
+                format_time(&turn.timestamp),
+                turn.content
+            ));
+        }
+        out
+    }
+}
+
+/// Timestamp format used in transcripts
+pub fn format_time(time: &DateTime<Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+}
+
+/// Directory holding session files
+fn sessions_dir() -> PathBuf {
+    config::titan_dir().join("sessions")
+}
+
+/// Check that a session ID is safe to use as a file name
+pub fn validate_id(id: &str) -> Result<()> {
+    let valid = !id.is_empty()
+        && id.len() <= 64
+        && !id.starts_with('.')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        bail!(
+            "Invalid session ID '{}': use up to 64 letters, digits, '-', '_' or '.'",
+            id
+        );
+    }
+    Ok(())
+}
+
+fn session_path(id: &str) -> Result<PathBuf> {
+    validate_id(id)?;
+    Ok(sessions_dir().join(format!("{}.json", id)))
+}
+
+/// Load a session, or `None` if it does not exist yet
+pub fn load(id: &str) -> Result<Option<Session>> {
+    let path = session_path(id)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let session = serde_json::from_str(&text)
+        .with_context(|| format!("Corrupt session file {}", path.display()))?;
+    Ok(Some(session))
+}
+
+/// Load a session that must already exist
+pub fn load_existing(id: &str) -> Result<Session> {
+    match load(id)? {
+        Some(session) => Ok(session),
+        None => bail!("No session named '{}' (see `titan sessions list`)", id),
+    }
+}
+
+/// Write a session, replacing the previous file atomically
+pub fn save(session: &Session) -> Result<()> {
+    let path = session_path(&session.id)?;
+    let dir = sessions_dir();
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(session)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, &path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Remove a session file
+pub fn delete(id: &str) -> Result<()> {
+    let path = session_path(id)?;
+    if !path.exists() {
+        bail!("No session named '{}' (see `titan sessions list`)", id);
+    }
+    std::fs::remove_file(&path).with_context(|| format!("Failed to delete {}", path.display()))
+}
+
+/// Every readable session, most recently updated first
+pub fn list() -> Result<Vec<Session>> {
+    let dir = sessions_dir();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut sessions = Vec::new();
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        if let Ok(session) = serde_json::from_str::<Session>(&text) {
+            sessions.push(session);
+        }
+    }
+
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.updated));
+    Ok(sessions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_ids() {
+        assert!(validate_id("incident-42").is_ok());
+        assert!(validate_id("2026.10_review").is_ok());
+        assert!(validate_id("../etc/passwd").is_err());
+        assert!(validate_id(".hidden").is_err());
+        assert!(validate_id("").is_err());
+    }
+
+    #[test]
+    fn test_messages_include_system_prompt() {
+        let mut session = Session::new("t", "vortex-v3");
+        session.system = Some("Be brief".to_string());
+        session.push(Message::user("hi"));
+        session.push(Message::assistant("hello"));
+
+        let roles: Vec<String> = session.messages().into_iter().map(|m| m.role).collect();
+        assert_eq!(roles, ["system", "user", "assistant"]);
+        assert!(session.to_markdown().contains("## VORTEX"));
+    }
+}
@@ -0,0 +1,319 @@
+//! Prompt templates for `titan ask --template`
+//!
+//! Templates are TOML files in `.titan/templates/` of the current project or
+//! in `~/.titan/templates/`, with `{{name}}` placeholders filled from
+//! `--var name=value`. A project template shadows a personal one of the same
+//! name.
+
+use super::{config, ui};
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Where a template was found
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    Project,
+    User,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Project => write!(f, "project"),
+            Source::User => write!(f, "user"),
+        }
+    }
+}
+
+/// A stored prompt
+#[derive(Debug, Deserialize)]
+pub struct Template {
+    #[serde(skip)]
+    pub name: String,
+    #[serde(skip)]
+    pub path: PathBuf,
+    #[serde(skip, default = "default_source")]
+    pub source: Source,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Model used when `--model` is not given
+    #[serde(default)]
+    pub model: Option<String>,
+    pub prompt: String,
+    /// Values for variables not passed with `--var`
+    #[serde(default)]
+    pub defaults: BTreeMap<String, String>,
+}
+
+fn default_source() -> Source {
+    Source::User
+}
+
+impl Template {
+    /// Placeholder names in the order they first appear
+    pub fn variables(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for (_, name, _) in placeholders(&self.prompt) {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+
+    /// Fill in the placeholders, failing on missing or unknown variables
+    pub fn render(&self, vars: &BTreeMap<String, String>) -> Result<String> {
+        let expected = self.variables();
+
+        let unknown: Vec<&str> = vars
+            .keys()
+            .filter(|k| !expected.contains(k))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            bail!(
+                "Template '{}' has no variable {} (it uses: {})",
+                self.name,
+                unknown.join(", "),
+                if expected.is_empty() {
+                    "none".to_string()
+                } else {
+                    expected.join(", ")
+                }
+            );
+        }
+
+        let missing: Vec<&str> = expected
+            .iter()
+            .map(String::as_str)
+            .filter(|n| !vars.contains_key(*n) && !self.defaults.contains_key(*n))
+            .collect();
+        if !missing.is_empty() {
+            let flags: Vec<String> = missing.iter().map(|n| format!("--var {}=...", n)).collect();
+            bail!(
+                "Template '{}' needs a value for {}: pass {}",
+                self.name,
+                missing.join(", "),
+                flags.join(" ")
+            );
+        }
+
+        let mut out = String::with_capacity(self.prompt.len());
+        let mut last = 0;
+        for (start, name, end) in placeholders(&self.prompt) {
+            out.push_str(&self.prompt[last..start]);
+            let value = vars.get(name).or_else(|| self.defaults.get(name));
+            out.push_str(value.map(String::as_str).unwrap_or_default());
+            last = end;
+        }
+        out.push_str(&self.prompt[last..]);
+        Ok(out.trim().to_string())
+    }
+}
+
+/// `{{name}}` placeholders as (start, name, end) byte positions
+///
+/// Braces around anything that is not a plain name are left as text, so
+/// prompts may contain code with `{{` in it.
+fn placeholders(text: &str) -> Vec<(usize, &str, usize)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = text[offset..].find("{{") {
+        let start = offset + open;
+        let Some(close) = text[start + 2..].find("}}") else {
+            break;
+        };
+        let name = text[start + 2..start + 2 + close].trim();
+        let end = start + 2 + close + 2;
+        if is_valid_name(name) {
+            found.push((start, name, end));
+            offset = end;
+        } else {
+            offset = start + 2;
+        }
+    }
+    found
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Parse a `--var name=value` argument
+pub fn parse_var(input: &str) -> Result<(String, String), String> {
+    let (name, value) = input
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{}'", input))?;
+    let name = name.trim();
+    if !is_valid_name(name) {
+        return Err(format!("invalid variable name '{}'", name));
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// Templates kept with the project in the current directory
+pub fn project_dir() -> PathBuf {
+    PathBuf::from(".titan").join("templates")
+}
+
+/// Personal templates
+pub fn user_dir() -> PathBuf {
+    config::titan_dir().join("templates")
+}
+
+fn read(path: PathBuf, name: &str, source: Source) -> Result<Template> {
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut template: Template =
+        toml::from_str(&text).with_context(|| format!("Invalid template {}", path.display()))?;
+    template.name = name.to_string();
+    template.path = path;
+    template.source = source;
+    Ok(template)
+}
+
+/// Load a template by name, preferring the project's copy
+pub fn load(name: &str) -> Result<Template> {
+    if !is_valid_name(name) {
+        bail!("Invalid template name '{}'", name);
+    }
+    for (dir, source) in [(project_dir(), Source::Project), (user_dir(), Source::User)] {
+        let path = dir.join(format!("{}.toml", name));
+        if path.exists() {
+            return read(path, name, source);
+        }
+    }
+    bail!("No template named '{}' (see `titan templates list`)", name)
+}
+
+/// Every template, sorted by name; project templates shadow personal ones
+///
+/// Files that cannot be read or parsed are skipped with a warning, so one
+/// broken template does not hide the rest.
+pub fn list() -> Result<Vec<Template>> {
+    let mut templates: BTreeMap<String, Template> = BTreeMap::new();
+    for (dir, source) in [(user_dir(), Source::User), (project_dir(), Source::Project)] {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries {
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(e) => {
+                    ui::eprint_warning(&format!("Could not read {}: {}", dir.display(), e));
+                    continue;
+                }
+            };
+            if path.extension().and_then(|e| e.to_str()) != Some("toml") {
+                continue;
+            }
+            let Some(name) = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            match read(path, &name, source) {
+                Ok(template) => {
+                    templates.insert(name, template);
+                }
+                Err(e) => ui::eprint_warning(&format!("{}, skipped", e)),
+            }
+        }
+    }
+    Ok(templates.into_values().collect())
+}
+
+/// Starting point written by `titan templates new`
+const SKELETON: &str = r#"description = ""
+
+# Model used when --model is not given (an ID or alias)
+# model = "vortex-v3"
+
+prompt = """
+Describe the task here. Placeholders such as {{service}} are filled
+from --var service=... when the template is used.
+"""
+
+# Values for placeholders not passed with --var
+[defaults]
+# service = "hermes"
+"#;
+
+/// Write a new template skeleton and return its path
+pub fn create(name: &str, project: bool, force: bool) -> Result<PathBuf> {
+    if !is_valid_name(name) {
+        bail!(
+            "Invalid template name '{}': use up to 64 letters, digits, '-', '_' or '.'",
+            name
+        );
+    }
+    let dir = if project { project_dir() } else { user_dir() };
+    let path = dir.join(format!("{}.toml", name));
+    if path.exists() && !force {
+        bail!(
+            "Template {} already exists (use --force to overwrite)",
+            path.display()
+        );
+    }
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    std::fs::write(&path, SKELETON)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(prompt: &str) -> Template {
+        let mut template: Template = toml::from_str(&format!(
+            "prompt = '''{}'''\n[defaults]\nwindow = \"1h\"\n",
+            prompt
+        ))
+        .unwrap();
+        template.name = "triage".to_string();
+        template
+    }
+
+    #[test]
+    fn test_render_fills_variables_and_defaults() {
+        let t = template("Why is {{ service }} failing over {{window}}? Check {{service}}.");
+        assert_eq!(t.variables(), ["service", "window"]);
+
+        let vars = BTreeMap::from([("service".to_string(), "hermes".to_string())]);
+        assert_eq!(
+            t.render(&vars).unwrap(),
+            "Why is hermes failing over 1h? Check hermes."
+        );
+    }
+
+    #[test]
+    fn test_missing_and_unknown_variables_fail() {
+        let t = template("Look at {{service}} and {{ {not: a var} }}");
+        let missing = t.render(&BTreeMap::new()).unwrap_err().to_string();
+        assert!(missing.contains("--var service=..."), "{}", missing);
+
+        let vars = BTreeMap::from([
+            ("service".to_string(), "hermes".to_string()),
+            ("sevrice".to_string(), "typo".to_string()),
+        ]);
+        let unknown = t.render(&vars).unwrap_err().to_string();
+        assert!(unknown.contains("no variable sevrice"), "{}", unknown);
+
+        assert_eq!(
+            parse_var("window=2h"),
+            Ok(("window".to_string(), "2h".to_string()))
+        );
+        assert!(parse_var("window").is_err());
+    }
+}
@@ -49,6 +49,11 @@ pub fn print_warning(message: &str) {
     println!("  {} {}", "⚠".yellow().bold(), message.yellow());
 }
 
+/// Print a warning message to stderr, leaving stdout to the command's result
+pub fn eprint_warning(message: &str) {
+    eprintln!("  {} {}", "⚠".yellow().bold(), message.yellow());
+}
+
 /// Print an info message
 #[allow(dead_code)]
 pub fn print_info(message: &str) {
@@ -76,3 +81,12 @@ pub fn status_indicator(online: bool) -> String {
         "○".red().to_string()
     }
 }
+
+/// Format a byte count as B, KB or MB
+pub fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1_048_575 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
@@ -0,0 +1,300 @@
+//! VORTEX usage ledger
+//!
+//! Every completion is appended to `~/.titan/usage.jsonl` with its token
+//! counts and its cost under the `[usage.prices]` table at the time of the
+//! call. `titan usage` summarizes the ledger, and the `[usage]` budgets are
+//! checked against it before each query. The ledger is rotated like the
+//! status history, so the oldest calls eventually drop out of the totals.
+
+use super::config::{self, UsageConfig};
+use super::rotate;
+use super::vortex::{Completion, Message};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Rotate the ledger once it grows past this size
+const MAX_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Number of rotated files kept next to the active one
+const GENERATIONS: usize = 3;
+
+/// One VORTEX call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record {
+    pub timestamp: DateTime<Utc>,
+    pub model: String,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// Set when VORTEX did not report usage and the counts were estimated
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub estimated: bool,
+    /// Cost at the configured price, if the model has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl Record {
+    /// Describe a completion of `messages`, estimating counts VORTEX did
+    /// not report
+    pub fn new(
+        completion: &Completion,
+        messages: &[Message],
+        session: Option<&str>,
+        config: &UsageConfig,
+    ) -> Self {
+        let reported = completion
+            .usage
+            .as_ref()
+            .filter(|u| u.prompt_tokens + u.completion_tokens > 0);
+        let (prompt_tokens, completion_tokens, estimated) = match reported {
+            Some(usage) => (usage.prompt_tokens, usage.completion_tokens, false),
+            None => {
+                let prompt = messages.iter().map(|m| estimate_tokens(&m.content)).sum();
+                (prompt, completion.completion_tokens(), true)
+            }
+        };
+        let cost = config.prices.get(&completion.model).map(|price| {
+            (prompt_tokens as f64 * price.input + completion_tokens as f64 * price.output)
+                / 1_000_000.0
+        });
+
+        Self {
+            timestamp: Utc::now(),
+            model: completion.model.clone(),
+            prompt_tokens,
+            completion_tokens,
+            estimated,
+            cost,
+            session: session.map(str::to_string),
+            request_id: completion.id.clone(),
+        }
+    }
+}
+
+/// Rough token count of a text, at four characters per token
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64 + 3) / 4
+}
+
+fn ledger_path() -> PathBuf {
+    config::titan_dir().join("usage.jsonl")
+}
+
+/// Append a record to the ledger
+pub fn record(record: &Record) -> Result<()> {
+    let path = ledger_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    rotate::rotate_if_needed(&path, MAX_BYTES, GENERATIONS)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(record)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Every readable record, oldest first; malformed lines are skipped
+pub fn load() -> Result<Vec<Record>> {
+    let mut records = Vec::new();
+    for path in rotate::files(&ledger_path(), GENERATIONS)
+        .iter()
+        .filter(|p| p.exists())
+    {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        records.extend(
+            text.lines()
+                .filter_map(|line| serde_json::from_str::<Record>(line).ok()),
+        );
+    }
+    Ok(records)
+}
+
+/// Usage of one group of calls
+#[derive(Debug, Default, PartialEq)]
+pub struct Totals {
+    pub calls: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost: f64,
+    /// Calls whose model has no price
+    pub unpriced: u64,
+    /// Calls with estimated token counts
+    pub estimated: u64,
+}
+
+impl Totals {
+    pub fn add(&mut self, record: &Record) {
+        self.calls += 1;
+        self.prompt_tokens += record.prompt_tokens;
+        self.completion_tokens += record.completion_tokens;
+        match record.cost {
+            Some(cost) => self.cost += cost,
+            None => self.unpriced += 1,
+        }
+        if record.estimated {
+            self.estimated += 1;
+        }
+    }
+
+    /// Fold another group into this one
+    pub fn merge(&mut self, other: &Totals) {
+        self.calls += other.calls;
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.cost += other.cost;
+        self.unpriced += other.unpriced;
+        self.estimated += other.estimated;
+    }
+}
+
+/// Sum records into groups named by `key`
+pub fn summarize<'a>(
+    records: impl IntoIterator<Item = &'a Record>,
+    key: impl Fn(&Record) -> String,
+) -> BTreeMap<String, Totals> {
+    let mut groups: BTreeMap<String, Totals> = BTreeMap::new();
+    for record in records {
+        groups.entry(key(record)).or_default().add(record);
+    }
+    groups
+}
+
+/// Local calendar day of a record, e.g. `2026-10-18`
+pub fn day_of(record: &Record) -> String {
+    record
+        .timestamp
+        .with_timezone(&Local)
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Cost of the calls made today, local time
+pub fn spent_today(records: &[Record]) -> f64 {
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    records
+        .iter()
+        .filter(|r| day_of(r) == today)
+        .filter_map(|r| r.cost)
+        .sum()
+}
+
+/// Describe the first exceeded budget, or `None` while within budget
+pub fn over_budget(
+    config: &UsageConfig,
+    records: &[Record],
+    session: Option<&str>,
+) -> Option<String> {
+    if let Some(limit) = config.daily_budget {
+        let spent = spent_today(records);
+        if spent >= limit {
+            return Some(format!(
+                "Daily VORTEX budget of {:.2} {} used up ({:.2} spent today)",
+                limit, config.currency, spent
+            ));
+        }
+    }
+
+    if let (Some(limit), Some(session)) = (config.session_budget, session) {
+        let spent: f64 = records
+            .iter()
+            .filter(|r| r.session.as_deref() == Some(session))
+            .filter_map(|r| r.cost)
+            .sum();
+        if spent >= limit {
+            return Some(format!(
+                "Budget of {:.2} {} for session {} used up ({:.2} spent)",
+                limit, config.currency, session, spent
+            ));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::config::Price;
+    use crate::utils::vortex::Usage;
+    use std::time::Duration;
+
+    fn completion(usage: Option<Usage>, chunks: u64) -> Completion {
+        Completion {
+            id: Some("req-1".to_string()),
+            model: "vortex-v3".to_string(),
+            text: "Hello there".to_string(),
+            finish_reason: Some("stop".to_string()),
+            usage,
+            tool_calls: Vec::new(),
+            latency: Duration::from_millis(300),
+            time_to_first_token: None,
+            interrupted: None,
+            chunks,
+        }
+    }
+
+    fn priced() -> UsageConfig {
+        let mut config = UsageConfig::default();
+        config.prices.insert(
+            "vortex-v3".to_string(),
+            Price {
+                input: 1.0,
+                output: 2.0,
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn test_records_use_reported_or_estimated_counts() {
+        let config = priced();
+        let messages = [Message::user("twelve chars")];
+        let reported = Usage {
+            prompt_tokens: 500_000,
+            completion_tokens: 250_000,
+        };
+
+        let record = Record::new(&completion(Some(reported), 0), &messages, None, &config);
+        assert!(!record.estimated);
+        assert_eq!(record.cost, Some(1.0));
+
+        let record = Record::new(&completion(None, 7), &messages, Some("s"), &config);
+        assert!(record.estimated);
+        assert_eq!((record.prompt_tokens, record.completion_tokens), (3, 7));
+    }
+
+    #[test]
+    fn test_budgets() {
+        let mut config = priced();
+        config.daily_budget = Some(1.5);
+        config.session_budget = Some(0.5);
+
+        let mut record = Record::new(&completion(None, 1), &[], Some("incident"), &config);
+        record.cost = Some(0.6);
+        let records = vec![record.clone()];
+
+        assert_eq!(over_budget(&config, &records, None), None);
+        let session = over_budget(&config, &records, Some("incident")).unwrap();
+        assert!(session.contains("session incident"), "{}", session);
+
+        let records = vec![record.clone(), record.clone(), record];
+        let daily = over_budget(&config, &records, None).unwrap();
+        assert!(daily.contains("Daily"), "{}", daily);
+
+        let by_model = summarize(&records, |r| r.model.clone());
+        assert_eq!(by_model["vortex-v3"].calls, 3);
+    }
+}
@@ -0,0 +1,903 @@
+//! VORTEX inference API client
+//!
+//! Talks to the chat completions API exposed by the VORTEX AI engine
+//! (`POST /v1/chat/completions`) and its model listing (`GET /v1/models`)
+//! through the shared `TitanClient`.
+//! Streamed responses may arrive as server-sent events or as JSON lines.
+//! Tools declared with `with_tools` may be requested through `tool_calls`.
+
+use super::client::{TimedResponse, TitanClient};
+use super::config::VortexConfig;
+use anyhow::{anyhow, bail, Context, Result};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// One message of a conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub role: String,
+    pub content: String,
+    /// Tools an assistant message asked to run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
+    /// Call a `tool` message answers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+impl Message {
+    pub fn new(role: &str, content: impl Into<String>) -> Self {
+        Self {
+            role: role.to_string(),
+            content: content.into(),
+            tool_calls: Vec::new(),
+            tool_call_id: None,
+        }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self::new("user", content)
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self::new("assistant", content)
+    }
+
+    pub fn system(content: impl Into<String>) -> Self {
+        Self::new("system", content)
+    }
+
+    /// The result of a tool call, sent back to VORTEX
+    pub fn tool(call_id: &str, content: impl Into<String>) -> Self {
+        Self {
+            tool_call_id: Some(call_id.to_string()),
+            ..Self::new("tool", content)
+        }
+    }
+}
+
+/// A tool VORTEX asked to run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolCall {
+    #[serde(default)]
+    pub id: String,
+    #[serde(rename = "type", default = "function_type")]
+    pub kind: String,
+    pub function: FunctionCall,
+}
+
+/// Name and JSON-encoded arguments of a tool call
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FunctionCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: String,
+}
+
+fn function_type() -> String {
+    "function".to_string()
+}
+
+/// Token counts reported by VORTEX
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Usage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+/// A finished completion
+#[derive(Debug)]
+pub struct Completion {
+    /// Request ID assigned by VORTEX
+    pub id: Option<String>,
+    /// Model that actually served the request
+    pub model: String,
+    pub text: String,
+    pub finish_reason: Option<String>,
+    pub usage: Option<Usage>,
+    /// Tools VORTEX asked to run before it answers
+    pub tool_calls: Vec<ToolCall>,
+    /// Time until the full response arrived
+    pub latency: Duration,
+    /// Time until the first streamed token arrived
+    pub time_to_first_token: Option<Duration>,
+    /// Why a stream ended early; the text received so far is kept
+    pub interrupted: Option<String>,
+    /// Number of streamed events that carried text
+    pub chunks: u64,
+}
+
+impl Completion {
+    /// Generated tokens, as reported or else counted from stream chunks
+    pub fn completion_tokens(&self) -> u64 {
+        self.usage
+            .as_ref()
+            .map(|u| u.completion_tokens)
+            .filter(|&n| n > 0)
+            .unwrap_or(self.chunks)
+    }
+}
+
+/// A model served by VORTEX, as listed by `GET /v1/models`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelInfo {
+    pub id: String,
+    /// Context window in tokens
+    #[serde(default, alias = "context_window")]
+    pub context_length: Option<u64>,
+    /// Largest completion the model produces, in tokens
+    #[serde(default)]
+    pub max_output_tokens: Option<u64>,
+    /// Input kinds accepted, e.g. `text` or `image`
+    #[serde(default, alias = "modality", deserialize_with = "one_or_many")]
+    pub modalities: Vec<String>,
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Model to move to when this one is deprecated
+    #[serde(default, alias = "successor")]
+    pub replacement: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Accept either a single string or a list of strings
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(OneOrMany::One(one)) => vec![one],
+        Some(OneOrMany::Many(many)) => many,
+    })
+}
+
+/// Sampling settings sent with every request
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Params {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Most tokens to generate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+    /// Sequences that end the completion when generated
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+    /// Seed for reproducible sampling
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+impl Params {
+    /// Most stop sequences the API accepts
+    pub const MAX_STOP: usize = 4;
+
+    /// Check value ranges, and the model's output limit when it is known
+    pub fn validate(&self, model: Option<&ModelInfo>) -> Result<()> {
+        if let Some(t) = self.temperature {
+            if !(0.0..=2.0).contains(&t) {
+                bail!("Temperature must be between 0 and 2, got {}", t);
+            }
+        }
+        if let Some(p) = self.top_p {
+            if !(p > 0.0 && p <= 1.0) {
+                bail!("Top-p must be above 0 and at most 1, got {}", p);
+            }
+        }
+        if self.stop.len() > Self::MAX_STOP {
+            bail!(
+                "At most {} stop sequences are allowed, got {}",
+                Self::MAX_STOP,
+                self.stop.len()
+            );
+        }
+        if self.stop.iter().any(String::is_empty) {
+            bail!("Stop sequences must not be empty");
+        }
+        if let Some(max) = self.max_tokens {
+            if max == 0 {
+                bail!("Max tokens must be at least 1");
+            }
+            let limit = model.and_then(|m| m.max_output_tokens.or(m.context_length));
+            if let (Some(model), Some(limit)) = (model, limit) {
+                if max > limit {
+                    bail!(
+                        "Max tokens {} exceeds the {} output tokens {} allows",
+                        max,
+                        limit,
+                        model.id
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `name=value` pairs of the settings that are set
+    pub fn describe(&self) -> Vec<String> {
+        let mut parts = Vec::new();
+        if let Some(t) = self.temperature {
+            parts.push(format!("temperature={}", t));
+        }
+        if let Some(p) = self.top_p {
+            parts.push(format!("top_p={}", p));
+        }
+        if let Some(max) = self.max_tokens {
+            parts.push(format!("max_tokens={}", max));
+        }
+        if !self.stop.is_empty() {
+            parts.push(format!("stop={:?}", self.stop));
+        }
+        if let Some(seed) = self.seed {
+            parts.push(format!("seed={}", seed));
+        }
+        parts
+    }
+}
+
+/// Request body of the chat completions API
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: &'a [Message],
+    stream: bool,
+    /// Asks for token usage in the final event of a stream
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<Value>,
+    #[serde(skip_serializing_if = "<[Value]>::is_empty")]
+    tools: &'a [Value],
+    #[serde(flatten)]
+    params: &'a Params,
+}
+
+/// Response body of the chat completions API
+///
+/// Both the `choices[].message.content` and the plain `text` shapes are
+/// accepted.
+#[derive(Deserialize)]
+struct RawCompletion {
+    id: Option<String>,
+    model: Option<String>,
+    #[serde(default)]
+    choices: Vec<RawChoice>,
+    text: Option<String>,
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize)]
+struct RawChoice {
+    message: Option<RawMessage>,
+    text: Option<String>,
+    finish_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawMessage {
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ToolCall>,
+}
+
+/// Client for the VORTEX inference API
+pub struct VortexClient {
+    http: TitanClient,
+    base_url: String,
+    timeout: Duration,
+    /// Tool definitions sent with every request
+    tools: Vec<Value>,
+    params: Params,
+}
+
+impl VortexClient {
+    /// Create a client for the endpoint in the `[vortex]` config section
+    pub fn new(config: &VortexConfig) -> Result<Self> {
+        Ok(Self {
+            http: TitanClient::new()?,
+            base_url: config.url.trim_end_matches('/').to_string(),
+            timeout: config.timeout,
+            tools: Vec::new(),
+            params: Params::default(),
+        })
+    }
+
+    /// Send these sampling settings with every request
+    pub fn with_params(mut self, params: Params) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Offer these tools (chat completions `tools` entries) on every request
+    pub fn with_tools(mut self, tools: Vec<Value>) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    /// Base URL of the inference API
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// List the models VORTEX serves
+    pub async fn models(&self) -> Result<Vec<ModelInfo>> {
+        let url = format!("{}/v1/models", self.base_url);
+        let response = self
+            .http
+            .request(Method::GET, &url, &[], None, self.timeout)
+            .await
+            .with_context(|| format!("Could not reach VORTEX at {}", self.base_url))?;
+
+        if !response.status.is_success() {
+            return Err(api_error("GET", &url, &response));
+        }
+        parse_models(&response.body)
+    }
+
+    /// Request a completion for the conversation and wait for the full answer
+    pub async fn complete(&self, model: &str, messages: &[Message]) -> Result<Completion> {
+        let url = format!("{}/v1/chat/completions", self.base_url);
+        let body = serde_json::to_value(ChatRequest {
+            model,
+            messages,
+            stream: false,
+            stream_options: None,
+            tools: &self.tools,
+            params: &self.params,
+        })?;
+
+        let response = self
+            .http
+            .request(Method::POST, &url, &[], Some(&body), self.timeout)
+            .await
+            .with_context(|| format!("Could not reach VORTEX at {}", self.base_url))?;
+
+        if !response.status.is_success() {
+            return Err(api_error("POST", &url, &response));
+        }
+
+        let raw: RawCompletion = serde_json::from_str(&response.body)
+            .context("VORTEX returned a response that is not a completion")?;
+        let mut completion = into_completion(raw, model)?;
+        completion.latency = response.latency;
+        Ok(completion)
+    }
+
+    /// Request a streamed completion, calling `on_token` as text arrives
+    ///
+    /// Failures before the first byte are returned as errors. A stream that
+    /// breaks later still yields the partial completion, with `interrupted`
+    /// describing what went wrong.
+    pub async fn complete_stream(
+        &self,
+        model: &str,
+        messages: &[Message],
+        mut on_token: impl FnMut(&str),
+    ) -> Result<Completion> {
+        let url = format!("{}/v1/chat/completions", self.base_url);
+        let body = serde_json::to_value(ChatRequest {
+            model,
+            messages,
+            stream: true,
+            stream_options: Some(serde_json::json!({ "include_usage": true })),
+            tools: &self.tools,
+            params: &self.params,
+        })?;
+
+        let start = Instant::now();
+        let mut response = self
+            .http
+            .post_stream(&url, &body, self.timeout)
+            .await
+            .with_context(|| format!("Could not reach VORTEX at {}", self.base_url))?;
+
+        if !response.status().is_success() {
+            let response = TimedResponse {
+                status: response.status(),
+                headers: response.headers().clone(),
+                latency: start.elapsed(),
+                body: response.text().await.unwrap_or_default(),
+            };
+            return Err(api_error("POST", &url, &response));
+        }
+
+        let mut state = StreamState::default();
+        let mut first_token = None;
+        let mut buffer: Vec<u8> = Vec::new();
+
+        let mut emit = |state: &mut StreamState, line: &[u8]| {
+            let line = String::from_utf8_lossy(line);
+            if let Some(token) = state.feed_line(line.trim_end_matches('\r')) {
+                first_token.get_or_insert_with(|| start.elapsed());
+                on_token(&token);
+            }
+        };
+
+        while !state.finished() {
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    buffer.extend_from_slice(&chunk);
+                    while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+                        let line: Vec<u8> = buffer.drain(..=pos).collect();
+                        emit(&mut state, &line[..line.len() - 1]);
+                        if state.finished() {
+                            break;
+                        }
+                    }
+                }
+                Ok(None) => {
+                    if !buffer.is_empty() {
+                        let rest = std::mem::take(&mut buffer);
+                        emit(&mut state, &rest);
+                    }
+                    if !state.finished() && state.finish_reason.is_none() {
+                        state.error = Some("connection closed before the response finished".into());
+                    }
+                    break;
+                }
+                Err(e) => {
+                    state.error = Some(if e.is_timeout() {
+                        format!("timed out after {}s", self.timeout.as_secs())
+                    } else {
+                        e.to_string()
+                    });
+                    break;
+                }
+            }
+        }
+
+        Ok(Completion {
+            id: state.id,
+            model: state.model.unwrap_or_else(|| model.to_string()),
+            text: state.text,
+            finish_reason: state.finish_reason,
+            usage: state.usage,
+            tool_calls: state.tool_calls,
+            latency: start.elapsed(),
+            time_to_first_token: first_token,
+            interrupted: state.error,
+            chunks: state.chunks,
+        })
+    }
+}
+
+/// Accumulates a streamed completion one event line at a time
+#[derive(Default)]
+struct StreamState {
+    id: Option<String>,
+    model: Option<String>,
+    text: String,
+    finish_reason: Option<String>,
+    usage: Option<Usage>,
+    /// Tool calls assembled from their streamed fragments
+    tool_calls: Vec<ToolCall>,
+    /// Number of events that carried text
+    chunks: u64,
+    done: bool,
+    error: Option<String>,
+}
+
+impl StreamState {
+    fn finished(&self) -> bool {
+        self.done || self.error.is_some()
+    }
+
+    /// Handle one SSE or NDJSON line, returning any text it carried
+    fn feed_line(&mut self, line: &str) -> Option<String> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(':') {
+            return None;
+        }
+
+        let payload = match line.strip_prefix("data:") {
+            Some(data) => data.trim(),
+            // Other SSE fields (event, id, retry) carry nothing we need
+            None if !line.starts_with('{') => return None,
+            None => line,
+        };
+        if payload == "[DONE]" {
+            self.done = true;
+            return None;
+        }
+
+        let event: Value = match serde_json::from_str(payload) {
+            Ok(event) => event,
+            Err(_) => {
+                self.error = Some(format!("unexpected stream data: {}", payload));
+                return None;
+            }
+        };
+
+        if let Some(error) = event.get("error") {
+            let message = error
+                .get("message")
+                .and_then(Value::as_str)
+                .or_else(|| error.as_str())
+                .unwrap_or("unknown error");
+            self.error = Some(format!("VORTEX reported an error: {}", message));
+            return None;
+        }
+
+        let text_of = |key: &str| event.pointer(key).and_then(Value::as_str);
+        if self.id.is_none() {
+            self.id = text_of("/id").map(str::to_string);
+        }
+        if self.model.is_none() {
+            self.model = text_of("/model").map(str::to_string);
+        }
+        if let Some(reason) =
+            text_of("/choices/0/finish_reason").or_else(|| text_of("/finish_reason"))
+        {
+            self.finish_reason = Some(reason.to_string());
+        }
+        if let Some(usage) = event.get("usage") {
+            self.usage = serde_json::from_value(usage.clone()).ok();
+        }
+        if event.get("done").and_then(Value::as_bool) == Some(true) {
+            self.done = true;
+        }
+        if let Some(Value::Array(calls)) = event.pointer("/choices/0/delta/tool_calls") {
+            self.feed_tool_calls(calls);
+        }
+
+        let token = text_of("/choices/0/delta/content")
+            .or_else(|| text_of("/choices/0/text"))
+            .or_else(|| text_of("/text"))
+            .or_else(|| text_of("/response"))
+            .filter(|t| !t.is_empty())?;
+        self.text.push_str(token);
+        self.chunks += 1;
+        Some(token.to_string())
+    }
+
+    /// Merge tool call fragments; arguments arrive a piece at a time
+    fn feed_tool_calls(&mut self, fragments: &[Value]) {
+        for (position, fragment) in fragments.iter().enumerate() {
+            let index = fragment
+                .get("index")
+                .and_then(Value::as_u64)
+                .map_or(position, |i| i as usize);
+            if self.tool_calls.len() <= index {
+                self.tool_calls.resize_with(index + 1, || ToolCall {
+                    kind: function_type(),
+                    ..ToolCall::default()
+                });
+            }
+            let call = &mut self.tool_calls[index];
+            let text_of = |key: &str| fragment.pointer(key).and_then(Value::as_str);
+            if let Some(id) = text_of("/id") {
+                call.id = id.to_string();
+            }
+            if let Some(name) = text_of("/function/name") {
+                call.function.name.push_str(name);
+            }
+            if let Some(arguments) = text_of("/function/arguments") {
+                call.function.arguments.push_str(arguments);
+            }
+        }
+    }
+}
+
+/// Convert the wire format, requiring some generated text
+fn into_completion(raw: RawCompletion, requested_model: &str) -> Result<Completion> {
+    let choice = raw.choices.into_iter().next();
+    let finish_reason = choice.as_ref().and_then(|c| c.finish_reason.clone());
+    let (content, tool_calls, choice_text) = match choice {
+        Some(RawChoice { message, text, .. }) => match message {
+            Some(message) => (message.content, message.tool_calls, text),
+            None => (None, Vec::new(), text),
+        },
+        None => (None, Vec::new(), None),
+    };
+    // A reply that only asks for tools carries no text
+    let text = match content.or(choice_text).or(raw.text) {
+        Some(text) => text,
+        None if !tool_calls.is_empty() => String::new(),
+        None => return Err(anyhow!("VORTEX response contained no completion text")),
+    };
+
+    Ok(Completion {
+        id: raw.id,
+        model: raw.model.unwrap_or_else(|| requested_model.to_string()),
+        text,
+        finish_reason,
+        usage: raw.usage,
+        tool_calls,
+        latency: Duration::ZERO,
+        time_to_first_token: None,
+        interrupted: None,
+        chunks: 0,
+    })
+}
+
+/// Read a model listing, either `{"data": [...]}`, `{"models": [...]}` or
+/// a bare array
+fn parse_models(body: &str) -> Result<Vec<ModelInfo>> {
+    let json: Value =
+        serde_json::from_str(body).context("VORTEX returned a model list that is not JSON")?;
+    let list = match json {
+        Value::Array(_) => json,
+        Value::Object(mut fields) => fields
+            .remove("data")
+            .or_else(|| fields.remove("models"))
+            .ok_or_else(|| anyhow!("VORTEX returned a model list without models"))?,
+        _ => return Err(anyhow!("VORTEX returned a model list without models")),
+    };
+    serde_json::from_value(list).context("VORTEX returned a malformed model list")
+}
+
+/// A non-success response from VORTEX
+///
+/// Kept in the error chain so a failed command can report the request
+/// behind it (see `last_error`).
+#[derive(Debug)]
+pub struct ApiError {
+    pub method: &'static str,
+    pub url: String,
+    pub status: StatusCode,
+    /// `x-request-id` header, or the ID in the error body
+    pub request_id: Option<String>,
+    /// Start of the response body
+    pub body: String,
+    /// Message the API gave, if any
+    message: Option<String>,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.message {
+            Some(ref message) => write!(f, "VORTEX returned {}: {}", self.status, message),
+            None if self.body.is_empty() => write!(f, "VORTEX returned {}", self.status),
+            None => write!(f, "VORTEX returned {}: {}", self.status, self.body),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Build an error from a non-success response, preferring the API's message
+fn api_error(method: &'static str, url: &str, response: &TimedResponse) -> anyhow::Error {
+    let json = serde_json::from_str::<Value>(&response.body).ok();
+    let message = json.as_ref().and_then(|json| {
+        json.pointer("/error/message")
+            .or_else(|| json.get("error"))
+            .or_else(|| json.get("message"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    });
+    let request_id = header(&response.headers, "x-request-id").or_else(|| {
+        json.as_ref()?
+            .pointer("/error/request_id")
+            .or_else(|| json.as_ref()?.get("request_id"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    });
+
+    anyhow::Error::new(ApiError {
+        method,
+        url: url.to_string(),
+        status: response.status,
+        request_id,
+        body: response.body.trim().chars().take(200).collect(),
+        message,
+    })
+}
+
+/// Whether a failed request is worth another attempt: no response arrived,
+/// or VORTEX was rate limiting or failing on its side
+pub fn is_transient(error: &anyhow::Error) -> bool {
+    match error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<ApiError>())
+    {
+        Some(api) => api.status == StatusCode::TOO_MANY_REQUESTS || api.status.is_server_error(),
+        None => error
+            .chain()
+            .any(|cause| cause.downcast_ref::<reqwest::Error>().is_some()),
+    }
+}
+
+fn header(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completion_shapes() {
+        let chat: RawCompletion = serde_json::from_str(
+            r#"{"id":"req-1","model":"vortex-v3","choices":[{"message":{"role":"assistant","content":"Hi"},"finish_reason":"stop"}],"usage":{"prompt_tokens":5,"completion_tokens":1}}"#,
+        )
+        .unwrap();
+        let completion = into_completion(chat, "vortex-v3").unwrap();
+        assert_eq!(completion.text, "Hi");
+        assert_eq!(completion.finish_reason.as_deref(), Some("stop"));
+        assert_eq!(completion.usage.unwrap().completion_tokens, 1);
+
+        let plain: RawCompletion = serde_json::from_str(r#"{"text":"Hello"}"#).unwrap();
+        let completion = into_completion(plain, "vortex-lite").unwrap();
+        assert_eq!(completion.text, "Hello");
+        assert_eq!(completion.model, "vortex-lite");
+    }
+
+    #[test]
+    fn test_model_listing_shapes() {
+        let models = parse_models(
+            r#"{"object":"list","data":[{"id":"vortex-v3","context_window":32768,"modality":"text"},{"id":"vortex-v2","deprecated":true,"successor":"vortex-v3","modalities":["text","image"]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(models[0].context_length, Some(32768));
+        assert_eq!(models[0].modalities, ["text"]);
+        assert!(models[1].deprecated);
+        assert_eq!(models[1].replacement.as_deref(), Some("vortex-v3"));
+
+        let bare = parse_models(r#"[{"id":"vortex-lite"}]"#).unwrap();
+        assert!(bare[0].modalities.is_empty());
+        assert!(parse_models(r#"{"error":"nope"}"#).is_err());
+    }
+
+    #[test]
+    fn test_sse_stream() {
+        let mut state = StreamState::default();
+        let lines = [
+            ": keep-alive",
+            r#"data: {"id":"req-2","model":"vortex-v3","choices":[{"delta":{"content":"Hel"}}]}"#,
+            "",
+            r#"data: {"choices":[{"delta":{"content":"lo"},"finish_reason":"stop"}],"usage":{"prompt_tokens":3,"completion_tokens":2}}"#,
+            "data: [DONE]",
+        ];
+        let tokens: Vec<String> = lines.iter().filter_map(|l| state.feed_line(l)).collect();
+
+        assert_eq!(tokens, ["Hel", "lo"]);
+        assert_eq!(state.text, "Hello");
+        assert_eq!(state.id.as_deref(), Some("req-2"));
+        assert_eq!(state.finish_reason.as_deref(), Some("stop"));
+        assert!(state.finished() && state.error.is_none());
+    }
+
+    #[test]
+    fn test_tool_calls() {
+        let mut state = StreamState::default();
+        let lines = [
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"id":"call-1","type":"function","function":{"name":"service_status","arguments":""}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"service\":"}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"hermes\"}"}}]},"finish_reason":"tool_calls"}]}"#,
+            "data: [DONE]",
+        ];
+        for line in lines {
+            assert_eq!(state.feed_line(line), None);
+        }
+        assert_eq!(state.tool_calls.len(), 1);
+        assert_eq!(state.tool_calls[0].id, "call-1");
+        assert_eq!(state.tool_calls[0].function.name, "service_status");
+        assert_eq!(
+            state.tool_calls[0].function.arguments,
+            r#"{"service":"hermes"}"#
+        );
+
+        let raw: RawCompletion = serde_json::from_str(
+            r#"{"choices":[{"message":{"role":"assistant","content":null,"tool_calls":[{"id":"call-2","type":"function","function":{"name":"deploy_history","arguments":"{}"}}]},"finish_reason":"tool_calls"}]}"#,
+        )
+        .unwrap();
+        let completion = into_completion(raw, "vortex-v3").unwrap();
+        assert!(completion.text.is_empty());
+        assert_eq!(completion.tool_calls[0].function.name, "deploy_history");
+    }
+
+    #[test]
+    fn test_ndjson_stream_error() {
+        let mut state = StreamState::default();
+        state.feed_line(r#"{"response":"Partial"}"#);
+        state.feed_line(r#"{"error":{"message":"model overloaded"}}"#);
+
+        assert_eq!(state.text, "Partial");
+        assert_eq!(
+            state.error.as_deref(),
+            Some("VORTEX reported an error: model overloaded")
+        );
+    }
+
+    #[test]
+    fn test_params_validation() {
+        let model: ModelInfo =
+            serde_json::from_str(r#"{"id":"vortex-lite","max_output_tokens":2048}"#).unwrap();
+        let params = Params {
+            temperature: Some(0.0),
+            max_tokens: Some(2048),
+            seed: Some(7),
+            ..Params::default()
+        };
+        assert!(params.validate(Some(&model)).is_ok());
+
+        let body = serde_json::to_value(ChatRequest {
+            model: "vortex-lite",
+            messages: &[],
+            stream: false,
+            stream_options: None,
+            tools: &[],
+            params: &params,
+        })
+        .unwrap();
+        assert_eq!(body["seed"], 7);
+        assert!(body.get("top_p").is_none() && body.get("stop").is_none());
+
+        let too_long = Params {
+            max_tokens: Some(4096),
+            ..Params::default()
+        };
+        let err = too_long.validate(Some(&model)).unwrap_err().to_string();
+        assert!(err.contains("2048"), "{}", err);
+        assert!(too_long.validate(None).is_ok());
+
+        let hot = Params {
+            temperature: Some(2.5),
+            ..Params::default()
+        };
+        assert!(hot.validate(None).is_err());
+    }
+
+    #[test]
+    fn test_api_error_message() {
+        let response = |body: &str| TimedResponse {
+            status: StatusCode::BAD_REQUEST,
+            headers: HeaderMap::new(),
+            body: body.to_string(),
+            latency: Duration::ZERO,
+        };
+        let url = "http://vortex/v1/chat/completions";
+        let err = api_error(
+            "POST",
+            url,
+            &response(r#"{"error":{"message":"unknown model","request_id":"req-7"}}"#),
+        );
+        assert_eq!(
+            err.to_string(),
+            "VORTEX returned 400 Bad Request: unknown model"
+        );
+        let details = err.downcast_ref::<ApiError>().unwrap();
+        assert_eq!(details.request_id.as_deref(), Some("req-7"));
+
+        let plain = api_error("POST", url, &response("  upstream timeout \n"));
+        assert_eq!(
+            plain.to_string(),
+            "VORTEX returned 400 Bad Request: upstream timeout"
+        );
+    }
+
+    #[test]
+    fn test_transient_errors() {
+        let error = |code| {
+            let response = TimedResponse {
+                status: StatusCode::from_u16(code).unwrap(),
+                headers: HeaderMap::new(),
+                body: String::new(),
+                latency: Duration::ZERO,
+            };
+            api_error("POST", "http://vortex/v1/chat/completions", &response)
+        };
+        assert!(is_transient(&error(429)));
+        assert!(is_transient(&error(503)));
+        assert!(is_transient(&error(500).context("Batch prompt failed")));
+        assert!(!is_transient(&error(400)));
+        assert!(!is_transient(&error(401)));
+        assert!(!is_transient(&anyhow::anyhow!(
+            "Invalid response from VORTEX"
+        )));
+    }
+}